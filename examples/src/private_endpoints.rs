@@ -20,7 +20,7 @@ fn main() {
         Err(e) => println!("Error: {}", e),
     }     
 
-    let order_history = api.orders.history(BTCUSD.to_owned()); // Use None if you don't want a pair 
+    let order_history = api.orders.history(BTCUSD); // Use None if you don't want a pair 
     match order_history {
         Ok(orders) => {
             for order in &orders {
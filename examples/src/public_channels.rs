@@ -1,7 +1,7 @@
 extern crate bitfinex;
 
 use bitfinex::{ errors::*, events::*, websockets::* };
-use bitfinex::{ pairs::*, precision::* };
+use bitfinex::{ candles::*, pairs::*, precision::* };
 
 struct WebSocketHandler;
 
@@ -47,19 +47,19 @@ fn main() {
     web_socket.connect().unwrap(); // check error
 
     // TICKER
-    web_socket.subscribe_ticker(BTCUSD, EventType::Trading);
+    web_socket.subscribe_ticker(BTCUSD);
 
     // TRADES
-    web_socket.subscribe_trades(BTCUSD, EventType::Trading);
+    web_socket.subscribe_trades(BTCUSD);
 
     // BOOKS
-    web_socket.subscribe_books(BTCUSD, EventType::Trading, P0, "F0", 25);
+    web_socket.subscribe_books(BTCUSD, P0, "F0", 25);
 
     // RAW BOOKS
-    web_socket.subscribe_raw_books(BTCUSD, EventType::Trading);
+    web_socket.subscribe_raw_books(BTCUSD);
 
     // CANDLES
-    web_socket.subscribe_candles(BTCUSD, "1m");
+    web_socket.subscribe_candles(&CandleKey::trading(Timeframe::OneMinute, BTCUSD));
 
     web_socket.event_loop().unwrap(); // check error
 }
@@ -4,6 +4,9 @@ use bitfinex::api::*;
 use bitfinex::pairs::*;
 use bitfinex::currency::*;
 use bitfinex::precision::*;
+use bitfinex::candles::*;
+use bitfinex::book::BookParams;
+use bitfinex::trades::TradeHistoryParams;
 
 fn main() {
     let api = Bitfinex::new(None, None);
@@ -22,7 +25,7 @@ fn main() {
     }
 
     // TRADES
-    let trading_pairs = api.trades.trading_pair(ETHUSD);
+    let trading_pairs = api.trades.trading_pair(ETHUSD, &TradeHistoryParams::new());
     match trading_pairs {
         Ok(trades) => {
             for trade in &trades {
@@ -32,18 +35,18 @@ fn main() {
         Err(e) => println!("Error: {}", e),
     }   
 
-    let funding_currency = api.trades.funding_currency(USD);
+    let funding_currency = api.trades.funding_currency(USD, &TradeHistoryParams::new());
     match funding_currency {
         Ok(trades) => {
             for trade in &trades {
-                println!("Funding => amount: {:?}  price: {:?}", trade.amount, trade.price);
+                println!("Funding => amount: {:?}  rate: {:?}", trade.amount, trade.rate);
             }    
         },
         Err(e) => println!("Error: {}", e),
     }        
 
     // BOOK
-    let trading_pairs = api.book.trading_pair(ETHUSD, P0);
+    let trading_pairs = api.book.trading_pair(ETHUSD, P0, &BookParams::new());
     match trading_pairs {
         Ok(books) => {
             for book in &books {
@@ -53,7 +56,7 @@ fn main() {
         Err(e) => println!("Error: {}", e),
     }   
 
-    let funding_currency = api.book.funding_currency(USD, P0);
+    let funding_currency = api.book.funding_currency(USD, P0, &BookParams::new());
     match funding_currency {
         Ok(books) => {
             for book in &books {
@@ -64,13 +67,13 @@ fn main() {
     }  
 
     // CANDLES
-    let last = api.candles.last(ETHUSD, "1m");
+    let last = api.candles.last(&CandleKey::trading(Timeframe::OneMinute, ETHUSD));
     match last {
         Ok(answer) => println!("Candle Last => High: {:?} low: {:?}", answer.high, answer.low),
         Err(e) => println!("Error: {}", e),
     }    
 
-    let history = api.candles.history(ETHUSD, "12h", &CandleHistoryParams::new());
+    let history = api.candles.history(&CandleKey::trading(Timeframe::TwelveHours, ETHUSD), &CandleHistoryParams::new());
     match history {
         Ok(candles) => {
             for candle in &candles {
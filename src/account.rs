@@ -1,15 +1,48 @@
-use client::*;
-use errors::*;
+use crate::bolt11::Bolt11;
+use crate::client::*;
+use crate::config::Config;
+use crate::errors::*;
+use crate::history::{HistoryEntry, HistoryPager, PageParams};
+use crate::number::{self, Number};
+use crate::notification::{Notification, NotificationStatus};
+use crate::symbol::{FundingCurrency, TradingPair};
 use log::*;
-use serde_json::{from_str, Value};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::thread;
+use std::time::{Duration, Instant};
+
+string_enum! {
+    pub enum WalletType {
+        Exchange => "exchange",
+        Margin => "margin",
+        Funding => "funding",
+    }
+}
+
+string_enum! {
+    /// Progress of a deposit or withdrawal.
+    pub enum MovementStatus {
+        Pending => "PENDING",
+        PendingReview => "PENDING REVIEW",
+        Unconfirmed => "UNCONFIRMED",
+        Processing => "PROCESSING",
+        Sending => "SENDING",
+        Completed => "COMPLETED",
+        PendingCancellation => "PENDING CANCELLATION",
+        Canceled => "CANCELED",
+    }
+}
 
 #[derive(Serialize, Deserialize)]
 pub struct Wallet {
-    pub wallet_type: String,
+    pub wallet_type: WalletType,
     pub currency: String,
-    pub balance: f64,
-    pub unsettled_interest: f64,
-    pub balance_available: Option<f64>,
+    pub balance: Number,
+    pub unsettled_interest: Number,
+    pub balance_available: Option<Number>,
     pub last_change: Option<String>,
     pub trade_details: Option<Value>,
 }
@@ -22,10 +55,10 @@ pub struct MarginBase {
 
 #[derive(Serialize, Deserialize)]
 pub struct Base {
-    pub user_profit_loss: f64,
-    pub user_swaps: f64,
-    pub margin_balance: f64,
-    pub margin_net: f64,
+    pub user_profit_loss: Number,
+    pub user_swaps: Number,
+    pub margin_balance: Number,
+    pub margin_net: Number,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -37,10 +70,10 @@ pub struct MarginSymbol {
 
 #[derive(Serialize, Deserialize)]
 pub struct Symbol {
-    pub tradable_balance: f64,
-    pub gross_balance: f64,
-    pub buy: f64,
-    pub sell: f64,
+    pub tradable_balance: Number,
+    pub gross_balance: Number,
+    pub buy: Number,
+    pub sell: Number,
 
     #[serde(skip_serializing)]
     _placeholder_1: Option<String>,
@@ -67,6 +100,303 @@ pub struct Funding {
     pub duration_lend: f64,
 }
 
+/// Auto-renew settings of a funding currency, `[CURRENCY, PERIOD, RATE, THRESHOLD]`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FundingAutoRenew {
+    pub currency: String,
+    /// Period in days
+    pub period: u32,
+    /// Rate of the renewed offers, 0 for FRR
+    pub rate: f64,
+    /// Maximum amount renewed, 0 for the whole balance
+    pub threshold: Number,
+}
+
+pub type FundingAutoNotification = Notification<Option<FundingAutoRenew>>;
+/// Answer of `funding/keep` and `funding/close`, which carry no data.
+pub type FundingUpdateNotification = Notification<Option<Value>>;
+
+/// Enables or disables the automatic renewal of funding offers.
+#[derive(Serialize, Clone, Debug)]
+pub struct FundingAutoReq {
+    status: i32,
+    currency: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    period: Option<u32>,
+}
+
+impl FundingAutoReq {
+    pub fn enable(currency: FundingCurrency) -> Self {
+        FundingAutoReq {
+            status: 1,
+            currency: currency.currency().to_owned(),
+            amount: None,
+            rate: None,
+            period: None,
+        }
+    }
+
+    pub fn disable(currency: FundingCurrency) -> Self {
+        FundingAutoReq { status: 0, ..FundingAutoReq::enable(currency) }
+    }
+
+    /// Maximum amount to renew, the whole balance is offered by default.
+    pub fn amount<N: fmt::Display>(mut self, amount: N) -> Self {
+        self.amount = Some(amount.to_string());
+        self
+    }
+
+    /// Rate in percent per day, the FRR is used by default.
+    pub fn rate<N: fmt::Display>(mut self, rate: N) -> Self {
+        self.rate = Some(rate.to_string());
+        self
+    }
+
+    /// Period in days, 2 by default.
+    pub fn period(mut self, period: u32) -> Self {
+        self.period = Some(period);
+        self
+    }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FundingKind {
+    Credit,
+    Loan,
+}
+
+/// Sets whether credits or loans are kept, i.e. renewed at the end of their period.
+#[derive(Serialize, Clone, Debug)]
+pub struct KeepFundingReq {
+    #[serde(rename = "type")]
+    kind: FundingKind,
+    id: i64,
+    changes: HashMap<i64, i32>,
+}
+
+impl KeepFundingReq {
+    pub fn new(kind: FundingKind, id: i64, keep: bool) -> Self {
+        KeepFundingReq { kind, id, changes: HashMap::new() }.keep(id, keep)
+    }
+
+    /// Adds another credit or loan of the same kind to the request.
+    pub fn keep(mut self, id: i64, keep: bool) -> Self {
+        self.changes.insert(id, keep as i32);
+        self
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct CloseFundingReq {
+    pub(crate) id: i64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct FundingAutoStatusReq<'a> {
+    pub(crate) currency: &'a str,
+}
+
+/// Leading fields of `info/user`; the ones after `group_id` are dropped.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UserInfo {
+    pub id: i64,
+    pub email: String,
+    pub username: String,
+    pub mts_account_create: i64,
+    #[serde(deserialize_with = "number::flag")]
+    pub verified: bool,
+    pub verification_level: Option<i32>,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    pub timezone: Option<String>,
+    pub locale: Option<String>,
+    pub company: Option<String>,
+    #[serde(deserialize_with = "number::flag", default)]
+    pub email_verified: bool,
+    #[serde(skip_serializing, default)]
+    _placeholder_2: Option<Value>,
+    #[serde(default)]
+    pub subaccount_type: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_3: Option<Value>,
+    #[serde(default)]
+    pub mts_master_account_create: Option<i64>,
+    #[serde(default)]
+    pub group_id: Option<i64>,
+}
+
+pub(crate) static USER_INFO_FIELDS: usize = 16;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MakerFees {
+    pub maker_fee: Number,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_4: Option<Value>,
+    /// Rebate paid on derivatives maker trades, negative for a rebate
+    pub deriv_rebate: Number,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TakerFees {
+    pub taker_fee_to_crypto: Number,
+    pub taker_fee_to_stable: Number,
+    pub taker_fee_to_fiat: Number,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    pub deriv_taker_fee: Number,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FeeRates {
+    pub maker: MakerFees,
+    pub taker: TakerFees,
+}
+
+/// Traded volume of a currency over the last 30 days, `curr` being
+/// "Total (USD)" for the sum over all currencies.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TradeVolume {
+    pub curr: String,
+    pub vol: Number,
+    #[serde(default)]
+    pub vol_safe: Option<Number>,
+    #[serde(default)]
+    pub vol_maker: Option<Number>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeoInfo {
+    pub leo_lev: Option<i32>,
+    pub leo_amount_avg: Option<Number>,
+}
+
+/// Fee rates and 30-day volume, as returned by `summary`.
+#[derive(Serialize, Clone, Debug)]
+pub struct AccountSummary {
+    pub fees: FeeRates,
+    pub trade_vol_30d: Vec<TradeVolume>,
+    pub leo: Option<LeoInfo>,
+}
+
+impl AccountSummary {
+    /// Total traded volume over the last 30 days, in USD.
+    pub fn total_volume_30d(&self) -> Option<Number> {
+        self.trade_vol_30d.iter().find(|v| v.curr == TOTAL_VOLUME).map(|v| v.vol)
+    }
+}
+
+pub(crate) static TOTAL_VOLUME: &str = "Total (USD)";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Login {
+    pub id: i64,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    pub mts: i64,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    pub ip: String,
+    #[serde(skip_serializing, default)]
+    _placeholder_3: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_4: Option<Value>,
+    #[serde(default)]
+    pub extra_info: Option<Value>,
+}
+
+/// Entry of `audit/hist`, a change made to the account settings.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub mts: i64,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    /// Kind of change, e.g. "leverage" or "api_key_create"
+    pub action: String,
+    /// Settings changed, their shape depends on `action`
+    pub changes: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_2: Option<Value>,
+    #[serde(default)]
+    pub ip: Option<String>,
+}
+
+string_enum! {
+    /// Scope of an API key permission.
+    pub enum PermissionScope {
+        Account => "account",
+        History => "history",
+        Orders => "orders",
+        Positions => "positions",
+        Funding => "funding",
+        Settings => "settings",
+        Wallets => "wallets",
+        Withdraw => "withdraw",
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Permission {
+    pub scope: PermissionScope,
+    #[serde(deserialize_with = "number::flag")]
+    pub read: bool,
+    #[serde(deserialize_with = "number::flag")]
+    pub write: bool,
+}
+
+/// Permissions of the API key used to sign the request.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(transparent)]
+pub struct Permissions(pub Vec<Permission>);
+
+impl Permissions {
+    pub fn can_read(&self, scope: PermissionScope) -> bool {
+        self.0.iter().any(|p| p.scope == scope && p.read)
+    }
+
+    pub fn can_write(&self, scope: PermissionScope) -> bool {
+        self.0.iter().any(|p| p.scope == scope && p.write)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct AccountHistoryParams {
+    /// Filter start (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<i64>,
+
+    /// Filter end (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<i64>,
+
+    /// Number of records requested (Max: 250)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+}
+
+impl AccountHistoryParams {
+    pub fn new() -> Self {
+        Self {
+            start: None,
+            end: None,
+            limit: Some(25),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Account {
     client: Client,
@@ -74,31 +404,111 @@ pub struct Account {
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct InvoiceReq {
-    pub wallet: String,
+    pub wallet: WalletType,
     pub currency: String,
     pub amount: String,
 }
 
+impl InvoiceReq {
+    /// Lightning invoice of `amount` BTC, credited to the exchange wallet.
+    pub fn new<N: fmt::Display>(amount: N) -> Self {
+        InvoiceReq { wallet: WalletType::Exchange, currency: LNX.into(), amount: amount.to_string() }
+    }
+}
+
+/// Lightning invoice created by `deposit/invoice`.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct InvoiceInfo {
     pub invoice_hash: String,
+    /// BOLT11 payment request to hand to the payer
     pub invoice: String,
-    pub _placeholder1: Option<String>,
-    pub _placehodler2: Option<String>,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
     pub amount: String,
 }
 
+impl InvoiceInfo {
+    /// Decodes the amount and expiry of the payment request.
+    pub fn bolt11(&self) -> Result<Bolt11> {
+        self.invoice.parse()
+    }
+}
+
+/// Whether a Lightning invoice was paid, looked up in the LNX movements.
+#[derive(Clone, Debug)]
+pub enum InvoiceStatus {
+    /// No deposit matches the invoice yet
+    Unpaid,
+    /// A deposit matches the invoice but hasn't completed
+    Pending(Box<Movement>),
+    Paid(Box<Movement>),
+}
+
+impl InvoiceStatus {
+    pub fn is_paid(&self) -> bool {
+        matches!(*self, InvoiceStatus::Paid(_))
+    }
+}
+
+pub(crate) static LNX: &str = "LNX";
+
+pub(crate) static INVOICE_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Looks for the LNX deposit paying an invoice among the movements since the invoice
+/// was created, as many per request as the endpoint allows.
+pub(crate) struct InvoiceSearch {
+    invoice: String,
+    hashes: Vec<String>,
+    pub(crate) params: MovementHistoryParams,
+}
+
+impl InvoiceSearch {
+    pub(crate) fn new(info: &InvoiceInfo) -> Result<Self> {
+        let bolt11 = info.bolt11()?;
+        let hashes = Some(info.invoice_hash.clone()).into_iter().chain(bolt11.payment_hash).collect();
+        let params = MovementHistoryParams {
+            start: Some(bolt11.timestamp as i64 * 1000),
+            limit: Some(MovementHistoryParams::MAX_LIMIT),
+            ..MovementHistoryParams::new()
+        };
+
+        Ok(InvoiceSearch { invoice: info.invoice.clone(), hashes, params })
+    }
+
+    /// A movement records where a deposit was sent as its destination address, which
+    /// for a Lightning deposit is the invoice. Its transaction id is compared to the
+    /// invoice hash and the payment hash as well.
+    pub(crate) fn pays_invoice(&self, movement: &Movement) -> bool {
+        let is = |field: &Option<String>, value: &str| {
+            field.as_deref().is_some_and(|field| field.eq_ignore_ascii_case(value))
+        };
+
+        is(&movement.destination_address, &self.invoice)
+            || self.hashes.iter().any(|hash| is(&movement.transaction_id, hash))
+    }
+}
+
+pub(crate) fn invoice_status(movement: Movement) -> InvoiceStatus {
+    if movement.status == MovementStatus::Completed {
+        InvoiceStatus::Paid(Box::new(movement))
+    } else {
+        InvoiceStatus::Pending(Box::new(movement))
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct LnAddressReq {
     pub method: String, 
-    pub wallet: String,
+    pub wallet: WalletType,
 }
 
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TransferReq {
-    pub from: String, 
-    pub to: String, 
+    pub from: WalletType,
+    pub to: WalletType,
     pub currency: String, 
     pub currency_to: Option<String>,
     pub amount: String, 
@@ -115,28 +525,28 @@ pub struct TransferResp {
     pub message_id: Option<String>, 
     pub info: TransferRespInfo,
     pub code: Option<String>,
-    pub status: String,
+    pub status: NotificationStatus,
     pub text: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TransferRespInfo {
     pub mts_updated: Option<i64>,
-    pub wallet_from: String, 
-    pub wallet_to: String, 
+    pub wallet_from: WalletType,
+    pub wallet_to: WalletType,
     #[serde(skip_serializing)]
     pub _unknown_field1: Option<String>,
     pub currency: String, 
     pub currency_to: String, 
     #[serde(skip_serializing)]
     pub _unknown_field2: Option<String>,
-    pub amount: f64,
+    pub amount: Number,
 }
 
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct WithdrawReq {
-    pub wallet: String,
+    pub wallet: WalletType,
     pub method: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub amount: Option<String>,
@@ -160,7 +570,7 @@ pub struct WithdrawResp {
     pub _placeholder: Option<Value>,
     pub data: WithdrawData,
     pub code: Option<i64>,
-    pub status: String,
+    pub status: NotificationStatus,
     pub text: Option<String>,
 }
 
@@ -170,11 +580,11 @@ pub struct WithdrawData {
     pub _placehodler1: Option<Value>,
     pub method: String, 
     pub payment_id: Option<String>, 
-    pub wallet: String,
-    pub amount: f32, 
+    pub wallet: WalletType,
+    pub amount: Number, 
     pub _placeholder2: Option<Value>,
     pub _placeholder3: Option<Value>,
-    pub withdraw_fee: f32,
+    pub withdraw_fee: Number,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -193,11 +603,11 @@ pub struct MovementResp {
     pub mts_updated: i64, 
     pub _placeholder2: Option<Value>,
     pub _placeholder3: Option<Value>,
-    pub status: String, 
+    pub status: MovementStatus,
     pub _placeholder4: Option<Value>,
     pub _placeholder5: Option<Value>,
-    pub amount: f64, 
-    pub fees: f64, 
+    pub amount: Number, 
+    pub fees: Number, 
     pub _placeholder6: Option<Value>,
     pub _placeholder7: Option<Value>,
     pub destination_address: Option<String>, 
@@ -208,7 +618,7 @@ pub struct MovementResp {
     pub movement_note: Option<String>, 
     pub _placeholder10: Option<Value>,
     pub _placeholder11: Option<Value>,
-    pub bank_fees: Option<f64>, 
+    pub bank_fees: Option<Number>, 
     pub bank_router_id: Option<i64>,
     pub _placeholder12: Option<Value>,
     pub _placeholder13: Option<Value>,
@@ -218,70 +628,376 @@ pub struct MovementResp {
     pub external_bank_mov_acc_info: Option<Value>, 
 }
 
+/// A deposit or withdrawal, as listed by `movements/hist`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Movement {
+    pub id: i64,
+    pub currency: String,
+    pub currency_name: String,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    pub mts_started: i64,
+    pub mts_updated: i64,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_4: Option<Value>,
+    pub status: MovementStatus,
+    #[serde(skip_serializing)]
+    _placeholder_5: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_6: Option<Value>,
+    /// Positive for deposits, negative for withdrawals
+    pub amount: Number,
+    pub fees: Number,
+    #[serde(skip_serializing)]
+    _placeholder_7: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_8: Option<Value>,
+    pub destination_address: Option<String>,
+    #[serde(skip_serializing)]
+    _placeholder_9: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_10: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_11: Option<Value>,
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    pub withdraw_transaction_note: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct MovementHistoryParams {
+    /// Filter start (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<i64>,
+
+    /// Filter end (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<i64>,
+
+    /// Number of records requested (Max: 1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+}
+
+impl MovementHistoryParams {
+    pub fn new() -> Self {
+        Self {
+            start: None,
+            end: None,
+            limit: Some(MOVEMENTS_LIMIT),
+        }
+    }
+}
+
+pub(crate) static MOVEMENTS_LIMIT: i32 = 25;
+
+impl HistoryEntry for Movement {
+    fn id(&self) -> i64 {
+        self.id
+    }
+
+    fn mts(&self) -> i64 {
+        self.mts_updated
+    }
+}
+
+impl PageParams for MovementHistoryParams {
+    const MAX_LIMIT: i32 = 1000;
+
+    fn limit(&self) -> i32 {
+        self.limit.unwrap_or(MOVEMENTS_LIMIT)
+    }
+
+    fn set_limit(&mut self, limit: i32) {
+        self.limit = Some(limit);
+    }
+
+    fn set_start(&mut self, start: i64) {
+        self.start = Some(start);
+    }
+
+    fn set_end(&mut self, end: i64) {
+        self.end = Some(end);
+    }
+}
+
+/// Paging state behind `MovementHistoryIter` and the async pages, see `HistoryPager`.
+/// Movements come newest first, paged by their `mts_updated`.
+#[derive(Debug)]
+pub(crate) struct MovementPager {
+    request: String,
+    pager: HistoryPager<MovementHistoryParams>,
+}
+
+impl MovementPager {
+    pub(crate) fn new(currency: Option<FundingCurrency>, params: MovementHistoryParams) -> Self {
+        MovementPager { request: movements_request(currency), pager: HistoryPager::new(params) }
+    }
+
+    /// Request and payload of the next page, `None` once the range is exhausted.
+    pub(crate) fn next_request(&self) -> Result<Option<(String, String)>> {
+        match self.pager.next_params() {
+            Some(params) => Ok(Some((self.request.clone(), serde_json::to_string(&params)?))),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn finish(&mut self) {
+        self.pager.finish();
+    }
+
+    pub(crate) fn advance(&mut self, page: Vec<Movement>) -> Result<Vec<Movement>> {
+        self.pager.advance(page)
+    }
+}
+
+/// Walks through the movements page by page, see `Account::movements_iter`.
+pub struct MovementHistoryIter<'a> {
+    account: &'a Account,
+    pager: MovementPager,
+    page: std::vec::IntoIter<Movement>,
+}
+
+impl Iterator for MovementHistoryIter<'_> {
+    type Item = Result<Movement>;
+
+    fn next(&mut self) -> Option<Result<Movement>> {
+        loop {
+            if let Some(movement) = self.page.next() {
+                return Some(Ok(movement));
+            }
+
+            let page = match self.pager.next_request() {
+                Ok(Some((request, payload))) => self.account.client.post_signed(request, payload)
+                    .and_then(|data| decode::<Vec<Movement>>(data.as_str()))
+                    .and_then(|page| self.pager.advance(page)),
+                Ok(None) => return None,
+                Err(e) => Err(e),
+            };
+
+            match page {
+                Ok(page) => self.page = page.into_iter(),
+                Err(e) => {
+                    self.pager.finish();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// Creates or renews the deposit address of a method in a wallet.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DepositAddressReq {
+    pub wallet: WalletType,
+    /// Method name, lowercase as returned by `Config::tx_method`, e.g. "bitcoin" or "lnx"
+    pub method: String,
+    /// 1 to replace the current address with a new one
+    pub op_renew: i32,
+}
+
+impl DepositAddressReq {
+    pub fn new<S: Into<String>>(method: S, wallet: WalletType) -> Self {
+        DepositAddressReq { wallet, method: method.into(), op_renew: 0 }
+    }
+
+    /// Asks for a new address instead of the current one.
+    pub fn renew(mut self) -> Self {
+        self.op_renew = 1;
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DepositAddress {
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    pub method: String,
+    pub currency: String,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    pub address: String,
+    /// Set for currencies sharing a pool address, `address` being the tag or memo then
+    #[serde(default)]
+    pub pool_address: Option<String>,
+}
+
+pub type DepositAddressNotification = Notification<DepositAddress>;
+
+/// Entry of `deposit/address/list`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DepositAddressEntry {
+    pub id: i64,
+    pub currency: String,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    pub address: String,
+    #[serde(default)]
+    pub pool_address: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct DepositAddressListReq<'a> {
+    pub(crate) method: &'a str,
+}
+
 impl Account {
     pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Account::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
         Account {
-            client: Client::new(api_key, secret_key),
+            client: Client::with_config(api_key, secret_key, config),
         }
     }
 
+    pub fn user_info(&self) -> Result<UserInfo> {
+        let data = self.client.post_signed("info/user".into(), "{}".to_string())?;
+
+        user_info(decode(data.as_str())?)
+    }
+
+    /// Fee rates and trading volume of the last 30 days.
+    pub fn summary(&self) -> Result<AccountSummary> {
+        let data = self.client.post_signed("summary".into(), "{}".to_string())?;
+
+        parse_summary(decode(data.as_str())?)
+    }
+
+    pub fn login_history(&self, params: &AccountHistoryParams) -> Result<Vec<Login>> {
+        let payload: String = serde_json::to_string(params)?;
+        let data = self.client.post_signed("logins/hist".into(), payload)?;
+
+        let logins: Vec<Login> = decode(data.as_str())?;
+
+        Ok(logins)
+    }
+
+    /// Permissions of the API key in use, e.g. to check for
+    /// `can_write(PermissionScope::Orders)` before trading.
+    pub fn permissions(&self) -> Result<Permissions> {
+        let data = self.client.post_signed("permissions".into(), "{}".to_string())?;
+
+        let permissions: Permissions = decode(data.as_str())?;
+
+        Ok(permissions)
+    }
+
+    /// Changes made to the account settings.
+    pub fn audit_history(&self, params: &AccountHistoryParams) -> Result<Vec<AuditEntry>> {
+        let payload: String = serde_json::to_string(params)?;
+        let data = self.client.post_signed("audit/hist".into(), payload)?;
+
+        let audit: Vec<AuditEntry> = decode(data.as_str())?;
+
+        Ok(audit)
+    }
+
     pub fn get_wallets(&self) -> Result<Vec<Wallet>> {
-        let payload: String = format!("{}", "{}");
+        let payload: String = "{}".to_string();
         let data = self.client.post_signed("wallets".into(), payload)?;
         debug!("Wallets response: {}", data.as_str());
-        let wallets: Vec<Wallet> = from_str(data.as_str())?;
+        let wallets: Vec<Wallet> = decode(data.as_str())?;
 
         Ok(wallets)
     }
 
     pub fn margin_base(&self) -> Result<MarginBase> {
-        let payload: String = format!("{}", "{}");
+        let payload: String = "{}".to_string();
 
         let data = self
             .client
             .post_signed("info/margin/base".into(), payload)?;
 
-        let margin: MarginBase = from_str(data.as_str())?;
+        let margin: MarginBase = decode(data.as_str())?;
 
         Ok(margin)
     }
 
-    pub fn margin_symbol<S>(&self, key: S) -> Result<MarginSymbol>
+    pub fn margin_symbol<P>(&self, pair: P) -> Result<MarginSymbol>
     where
-        S: Into<String>,
+        P: TryInto<TradingPair>,
+        Error: From<P::Error>,
     {
-        let payload: String = format!("{}", "{}");
-        let request: String = format!("info/margin/t{}", key.into());
+        let payload: String = "{}".to_string();
+        let request: String = format!("info/margin/{}", pair.try_into()?);
 
         let data = self.client.post_signed(request, payload)?;
 
-        let margin: MarginSymbol = from_str(data.as_str())?;
+        let margin: MarginSymbol = decode(data.as_str())?;
 
         Ok(margin)
     }
 
-    pub fn funding_info<S>(&self, key: S) -> Result<FundingInfo>
+    pub fn funding_info<C>(&self, currency: C) -> Result<FundingInfo>
     where
-        S: Into<String>,
+        C: TryInto<FundingCurrency>,
+        Error: From<C::Error>,
     {
-        let payload: String = format!("{}", "{}");
-        let request: String = format!("info/funding/f{}", key.into());
+        let payload: String = "{}".to_string();
+        let request: String = format!("info/funding/{}", currency.try_into()?);
 
         let data = self.client.post_signed(request, payload)?;
 
-        let info: FundingInfo = from_str(data.as_str())?;
+        let info: FundingInfo = decode(data.as_str())?;
 
         Ok(info)
     }
 
+    /// Current auto-renew settings of a currency, `None` when auto-renew is off.
+    pub fn funding_auto_renew_status<C>(&self, currency: C) -> Result<Option<FundingAutoRenew>>
+    where
+        C: TryInto<FundingCurrency>,
+        Error: From<C::Error>,
+    {
+        let currency = currency.try_into()?;
+        let payload: String = serde_json::to_string(&FundingAutoStatusReq { currency: currency.currency() })?;
+
+        let data = self.client.post_signed("funding/auto/status".into(), payload)?;
+
+        auto_renew_status(decode(data.as_str())?)
+    }
+
+    pub fn funding_auto_renew(&self, req: FundingAutoReq) -> Result<FundingAutoNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.funding_write("funding/auto", payload)
+    }
+
+    pub fn keep_funding(&self, req: KeepFundingReq) -> Result<FundingUpdateNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.funding_write("funding/keep", payload)
+    }
+
+    /// Returns a taken funding (loan or credit) before the end of its period.
+    pub fn close_funding(&self, id: i64) -> Result<FundingUpdateNotification> {
+        let payload: String = serde_json::to_string(&CloseFundingReq { id })?;
+        self.funding_write("funding/close", payload)
+    }
+
+    fn funding_write<T: DeserializeOwned>(&self, request: &str, payload: String) -> Result<T> {
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request.into(), payload)?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+
     // If this is the first time you are generating an LNX invoice on your account, you will first need to create a deposit address. To do this, call w/deposit/address with { method: 'LNX', wallet: 'exchange' }
     pub fn generate_invoice_address(&self)  -> Result<()> {
         let req = LnAddressReq {
             method: "LNX".to_owned(),
-            wallet: "exchange".to_owned(),
+            wallet: WalletType::Exchange,
         };
         let payload: String = serde_json::to_string(&req)?;
-        let request: String = format!("deposit/address");
+        let request: String = "deposit/address".to_string();
 
         debug!("Payload: {payload}");
         self.client.post_w_signed(request, payload)?;
@@ -291,61 +1007,218 @@ impl Account {
 
     pub fn generate_invoice(&self, req: InvoiceReq) -> Result<InvoiceInfo> {
         let payload: String = serde_json::to_string(&req)?;
-        let request: String = format!("deposit/invoice");
+        let request: String = "deposit/invoice".to_string();
         debug!("Payload: {payload}");
 
         let data = self.client.post_w_signed(request, payload)?;
 
-        let info: InvoiceInfo = from_str(data.as_str())?;
+        let info: InvoiceInfo = decode(data.as_str())?;
 
         Ok(info)
     }
 
+    /// Looks for the deposit paying `invoice` among the LNX movements since it was created.
+    pub fn invoice_status(&self, invoice: &InvoiceInfo) -> Result<InvoiceStatus> {
+        let search = InvoiceSearch::new(invoice)?;
+
+        for movement in self.movements_iter(FundingCurrency::new(LNX), search.params.clone()) {
+            let movement = movement?;
+            if search.pays_invoice(&movement) {
+                return Ok(invoice_status(movement));
+            }
+        }
+
+        Ok(InvoiceStatus::Unpaid)
+    }
+
+    /// Polls `invoice_status` until the invoice is paid or `timeout` elapses, returning
+    /// the last status seen. `Bolt11::expires_at` gives a sensible upper bound.
+    ///
+    /// Retryable errors are logged and the polling goes on; the error is only returned
+    /// when the last lookup before the deadline failed.
+    pub fn wait_for_payment(&self, invoice: &InvoiceInfo, timeout: Duration) -> Result<InvoiceStatus> {
+        // Too far away to be represented: wait until the invoice is paid
+        let deadline = Instant::now().checked_add(timeout);
+
+        loop {
+            let status = match self.invoice_status(invoice) {
+                Ok(status) if status.is_paid() => return Ok(status),
+                Ok(status) => Ok(status),
+                Err(e) if e.is_retryable() => {
+                    warn!("Invoice lookup failed, retrying: {}", e);
+                    Err(e)
+                }
+                Err(e) => return Err(e),
+            };
+
+            let now = Instant::now();
+            let wait = match deadline {
+                Some(deadline) if now >= deadline => return status,
+                Some(deadline) => INVOICE_POLL_INTERVAL.min(deadline - now),
+                None => INVOICE_POLL_INTERVAL,
+            };
+
+            thread::sleep(wait);
+        }
+    }
+
     pub fn transfer(&self, req: TransferReq) -> Result<TransferResp> {
         let payload: String = serde_json::to_string(&req)?;
-        let request: String = format!("transfer");
+        let request: String = "transfer".to_string();
         debug!("Payload: {payload}");
 
         let data = self.client.post_w_signed(request, payload)?;
         info!("Response: {}", data.as_str());
-        let info: TransferResp = from_str(data.as_str())?;
+        let info: TransferResp = decode(data.as_str())?;
 
         Ok(info)
     }
 
     pub fn withdraw(&self, req: WithdrawReq) -> Result<WithdrawResp> {
         let payload: String = serde_json::to_string(&req)?;
-        let request: String = format!("withdraw");
+        let request: String = "withdraw".to_string();
         debug!("Payload: {payload}");
 
         let data = self.client.post_w_signed(request, payload)?;
         info!("Response: {}", data.as_str());
-        let info: WithdrawResp = from_str(data.as_str())?;
+        let info: WithdrawResp = decode(data.as_str())?;
 
         Ok(info)
     }
 
     pub fn movement_info(&self, req: MovementReq) -> Result<MovementResp> {
         let payload: String = serde_json::to_string(&req)?;
-        let request: String = format!("movements/info");
+        let request: String = "movements/info".to_string();
         debug!("Payload: {payload}");
 
         let data = self.client.post_signed(request, payload)?;
         info!("Response: {}", data.as_str());
-        let info: MovementResp = from_str(data.as_str())?;
+        let info: MovementResp = decode(data.as_str())?;
 
         Ok(info)
     }
+
+    /// Deposits and withdrawals of a currency, or of every currency with `None`.
+    pub fn movements<C>(&self, currency: C, params: &MovementHistoryParams) -> Result<Vec<Movement>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        let payload: String = serde_json::to_string(params)?;
+        let data = self.client.post_signed(movements_request(currency.into()), payload)?;
+
+        let movements: Vec<Movement> = decode(data.as_str())?;
+
+        Ok(movements)
+    }
+
+    /// Iterates over every movement up to `params.end`, newest first, fetching
+    /// `params.limit` movements per request.
+    pub fn movements_iter<C>(&self, currency: C, params: MovementHistoryParams) -> MovementHistoryIter<'_>
+        where C: Into<Option<FundingCurrency>>
+    {
+        MovementHistoryIter {
+            account: self,
+            pager: MovementPager::new(currency.into(), params),
+            page: Vec::new().into_iter(),
+        }
+    }
+
+    pub fn deposit_address(&self, req: DepositAddressReq) -> Result<DepositAddressNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed("deposit/address".into(), payload)?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+
+    /// Deposit address of a currency, resolving its method with `Config::tx_method`.
+    /// Currencies on several networks fail there, use `deposit_address` with a method.
+    pub fn currency_deposit_address<C>(&self, currency: C, wallet: WalletType, renew: bool) -> Result<DepositAddressNotification>
+        where C: TryInto<FundingCurrency>, Error: From<C::Error>
+    {
+        let currency = currency.try_into()?;
+        let method = Config::with_client(self.client.clone())
+            .tx_method(currency.currency())?
+            .ok_or_else(|| Error::InvalidRequest(format!("no deposit method for {}", currency.currency())))?;
+
+        let req = DepositAddressReq::new(method, wallet);
+        self.deposit_address(if renew { req.renew() } else { req })
+    }
+
+    /// Addresses created for a deposit method.
+    pub fn deposit_addresses(&self, method: &str) -> Result<Vec<DepositAddressEntry>> {
+        let payload: String = serde_json::to_string(&DepositAddressListReq { method })?;
+        let data = self.client.post_signed("deposit/address/list".into(), payload)?;
+
+        let addresses: Vec<DepositAddressEntry> = decode(data.as_str())?;
+
+        Ok(addresses)
+    }
+}
+
+/// `movements/{currency}/hist` or `movements/hist` for every currency.
+pub(crate) fn movements_request(currency: Option<FundingCurrency>) -> String {
+    match currency {
+        Some(currency) => format!("movements/{}/hist", currency.currency()),
+        None => "movements/hist".into(),
+    }
+}
+
+/// `info/user` keeps growing; only the documented leading fields are decoded.
+pub(crate) fn user_info(mut fields: Vec<Value>) -> Result<UserInfo> {
+    fields.truncate(USER_INFO_FIELDS);
+
+    Ok(serde_json::from_value(Value::Array(fields))?)
+}
+
+/// Picks the fee rates (5th field), the 30-day volume (the list of `curr`
+/// objects) and the LEO discount (the object with `leo_lev`) out of `summary`,
+/// whose other fields are placeholders.
+pub(crate) fn parse_summary(fields: Vec<Value>) -> Result<AccountSummary> {
+    let fees = match fields.get(4) {
+        Some(fees) => serde_json::from_value(fees.clone())?,
+        None => return Err(Error::Other("Missing fee rates in summary".into())),
+    };
+
+    let mut trade_vol_30d = Vec::new();
+    let mut leo = None;
+
+    for field in fields.into_iter().skip(5) {
+        match field {
+            Value::Array(ref values) if values.first().is_some_and(|v| v.get("curr").is_some()) => {
+                trade_vol_30d = serde_json::from_value(field)?;
+            }
+            Value::Object(ref object) if object.contains_key("leo_lev") => {
+                leo = Some(serde_json::from_value(field)?);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(AccountSummary { fees, trade_vol_30d, leo })
+}
+
+/// `funding/auto/status` answers an empty array or `null` when auto-renew is off.
+pub(crate) fn auto_renew_status(status: Value) -> Result<Option<FundingAutoRenew>> {
+    match status {
+        Value::Null => Ok(None),
+        Value::Array(ref values) if values.is_empty() => Ok(None),
+        status => Ok(Some(serde_json::from_value(status)?)),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::from_str;
 
     #[test]
     fn test_transfer_resp() {
         let data = "[1690901416558,\"acc_tf\",null,null,[1690901416558,\"exchange\",\"exchange\",null,\"LNX\",\"BTC\",null,0.00034774],null,\"SUCCESS\",\"0.00034774 Bitcoin (Lightning Network) transfered from Exchange to Exchange\"]";
-        let _: TransferResp = from_str(data).expect("parsed"); 
+        let resp: TransferResp = from_str(data).expect("parsed");
+        assert!(resp.status.is_success());
+        assert_eq!(resp.info.wallet_from, WalletType::Exchange);
     }
 
     #[test]
@@ -357,6 +1230,104 @@ mod tests {
     #[test]
     fn test_movement_resp() {
         let data = "[24,\"EUR\",\"WIRE\",null,\"remark related to bank details\",1677086074000,1677086210000,null,null,\"COMPLETED\",null,null,-29.5,-0.5,null,null,null,null,null,null,null,\"testing note\",null,null,0,123,null,null,\"abcd-1234\",\"COMPLETED\",\"finished withdrawal in platform\",{\"router\":\"my-router\",\"meta\":{\"foo\":\"bar\"}}]";
-        let _: MovementResp = from_str(data).expect("parsed"); 
+        let resp: MovementResp = from_str(data).expect("parsed");
+        assert_eq!(resp.status, MovementStatus::Completed);
+        assert_eq!(MovementStatus::from("ON HOLD"), MovementStatus::Unknown("ON HOLD".into()));
+    }
+
+    #[test]
+    fn test_funding_auto_renew() {
+        let data = "[1575301281046,\"fa-req\",null,null,[\"USD\",2,0.0001,1000],null,\"SUCCESS\",\"auto-renew enabled\"]";
+        let resp: FundingAutoNotification = from_str(data).expect("parsed");
+        assert_eq!(resp.data.expect("settings").period, 2);
+
+        assert!(auto_renew_status(Value::Array(vec![])).unwrap().is_none());
+
+        let req = FundingAutoReq::enable(FundingCurrency::new("USD")).rate("0.02").period(30);
+        assert_eq!(serde_json::to_string(&req).unwrap(), "{\"status\":1,\"currency\":\"USD\",\"rate\":\"0.02\",\"period\":30}");
+
+        let req = KeepFundingReq::new(FundingKind::Credit, 123, false);
+        assert_eq!(serde_json::to_string(&req).unwrap(), "{\"type\":\"credit\",\"id\":123,\"changes\":{\"123\":0}}");
+    }
+
+    #[test]
+    fn test_invoice() {
+        let data = "[\"0001020304050607080900010203040506070809000102030405060708090102\",\"lnbc2500u1pvjluezpp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdq5xysxxatsyp3k7enxv4jsxqzpuaztrnwngzn3kdzw5hydlzf03qdgm2hdq27cqv3agm2awhz5se903vruatfhq77w3ls4evs3ch9zw97j25emudupq63nyw24cg27h2rspfj9srp\",null,null,\"0.0025\"]";
+        let info: InvoiceInfo = from_str(data).expect("parsed");
+        let bolt11 = info.bolt11().unwrap();
+        assert_eq!(bolt11.payment_hash.as_deref(), Some(info.invoice_hash.as_str()));
+        assert_eq!(bolt11.amount(), Some(info.amount.parse::<Number>().unwrap()));
+
+        let search = InvoiceSearch::new(&info).unwrap();
+        assert_eq!(serde_json::to_string(&search.params).unwrap(), "{\"start\":1496314658000,\"limit\":1000}");
+
+        // Paid to the invoice, or carrying its hash as transaction id
+        let data = format!(
+            "[[13293039,\"LNX\",\"Lightning Network\",null,null,1574175052000,1574181326000,null,null,\"COMPLETED\",null,null,0.0025,0,null,null,\"{}\",null,null,null,null,null],[13293040,\"LNX\",\"Lightning Network\",null,null,1574175052000,1574181326000,null,null,\"PENDING\",null,null,0.0025,0,null,null,null,null,null,null,\"{}\",null],[13293041,\"LNX\",\"Lightning Network\",null,null,1574175052000,1574181326000,null,null,\"COMPLETED\",null,null,0.001,0,null,null,\"lnbc1other\",null,null,null,\"ff\",null]]",
+            info.invoice,
+            info.invoice_hash.to_uppercase()
+        );
+        let mut movements: Vec<Movement> = from_str(&data).expect("parsed");
+        assert!(search.pays_invoice(&movements[0]));
+        assert!(search.pays_invoice(&movements[1]));
+        assert!(!search.pays_invoice(&movements[2]));
+
+        movements.truncate(1);
+        let movement = movements.pop().unwrap();
+        assert!(invoice_status(movement.clone()).is_paid());
+        let pending = Movement { status: MovementStatus::Pending, ..movement };
+        assert!(matches!(invoice_status(pending), InvoiceStatus::Pending(_)));
+
+        let req = InvoiceReq::new("0.0025");
+        assert_eq!(serde_json::to_string(&req).unwrap(), "{\"wallet\":\"exchange\",\"currency\":\"LNX\",\"amount\":\"0.0025\"}");
+    }
+
+    #[test]
+    fn test_movements() {
+        let data = "[[13293039,\"ETH\",\"ETHEREUM\",null,null,1574175052000,1574181326000,null,null,\"CANCELED\",null,null,-0.24,-0.00135,null,null,\"0x4e5d67a1f4f3e2b0b7c0b2b5c3c4e2d8b1f0a9c2\",null,null,null,\"0x523ec8945500\",\"\"]]";
+        let page: Vec<Movement> = from_str(data).expect("parsed");
+        assert_eq!(page[0].status, MovementStatus::Canceled);
+        assert_eq!(movements_request(Some(FundingCurrency::new("ETH"))), "movements/ETH/hist");
+
+        let mut pager = MovementPager::new(None, MovementHistoryParams { limit: Some(1), ..MovementHistoryParams::new() });
+        assert_eq!(pager.advance(page.clone()).unwrap().len(), 1);
+        assert_eq!(pager.next_request().unwrap().unwrap().1, "{\"end\":1574181326000,\"limit\":1}");
+        assert!(pager.advance(page).unwrap().is_empty());
+        assert_eq!(
+            pager.next_request().unwrap().unwrap(),
+            ("movements/hist".into(), "{\"start\":1574181326000,\"end\":1574181326000,\"limit\":1000}".into())
+        );
+
+        let data = "[1574335079000,\"acc_dep\",null,null,[null,\"BITCOIN\",\"BTC\",null,\"bc1qwxyz\",null],null,\"SUCCESS\",\"success\"]";
+        let resp: DepositAddressNotification = from_str(data).expect("parsed");
+        assert_eq!(resp.data.address, "bc1qwxyz");
+
+        let req = DepositAddressReq::new("bitcoin", WalletType::Exchange).renew();
+        assert_eq!(serde_json::to_string(&req).unwrap(), "{\"wallet\":\"exchange\",\"method\":\"bitcoin\",\"op_renew\":1}");
+    }
+
+    #[test]
+    fn test_account_info() {
+        let data = "[[\"account\",1,0],[\"orders\",1,1],[\"withdraw\",0,1]]";
+        let permissions: Permissions = from_str(data).expect("parsed");
+        assert!(permissions.can_write(PermissionScope::Orders));
+        assert!(!permissions.can_read(PermissionScope::Withdraw));
+        assert!(!permissions.can_write(PermissionScope::Funding));
+
+        let data = "[null,null,null,null,[[0.001,0.001,0.001,null,null,-0.0002],[0.002,0.002,0.002,null,null,0.00075]],null,[{\"curr\":\"BTC\",\"vol\":0.5},{\"curr\":\"Total (USD)\",\"vol\":25000.5}],null,null,null,null,{\"leo_lev\":0,\"leo_amount_avg\":0.002}]";
+        let summary = parse_summary(from_str(data).unwrap()).unwrap();
+        assert_eq!(summary.total_volume_30d(), Some("25000.5".parse::<Number>().unwrap()));
+        assert_eq!(summary.fees.taker.deriv_taker_fee, "0.00075".parse::<Number>().unwrap());
+        assert_eq!(summary.leo.expect("leo").leo_lev, Some(0));
+
+        let data = "[123,\"user@example.com\",\"user\",1527584450000,1,3,null,\"Europe/Berlin\",\"en_US\",null,1,null,null,null,null,null,null,null,null,null,null,1]";
+        let user = user_info(from_str(data).unwrap()).unwrap();
+        assert!(user.verified && user.email_verified);
+        assert_eq!(user.timezone.as_deref(), Some("Europe/Berlin"));
+
+        let data = "[[5721953,1614179023000,null,\"leverage\",{\"from\":10,\"to\":5},null,\"127.0.0.1\"]]";
+        let audit: Vec<AuditEntry> = from_str(data).expect("parsed");
+        assert_eq!(audit[0].action, "leverage");
+        assert_eq!(audit[0].ip.as_deref(), Some("127.0.0.1"));
     }
 }
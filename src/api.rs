@@ -1,32 +1,55 @@
-use book::*;
-use ticker::*;
-use trades::*;
-use candles::*;
-use orders::*;
-use account::*;
-use ledger::*;
+use crate::book::*;
+use crate::ticker::*;
+use crate::trades::*;
+use crate::calc::*;
+use crate::candles::*;
+use crate::derivatives::*;
+use crate::orders::*;
+use crate::positions::*;
+use crate::funding::Funding;
+use crate::account::*;
+use crate::ledger::*;
+use crate::stats::*;
+use crate::client::ClientConfig;
+use crate::config::Config;
 
 #[derive(Clone)]
 pub struct Bitfinex {
+    pub config: Config,
     pub book: Book,
     pub ticker: Ticker,
     pub trades: Trades,
     pub candles: Candles,
+    pub derivatives: Derivatives,
+    pub stats: Stats,
+    pub calc: Calc,
     pub orders: Orders,
+    pub positions: Positions,
+    pub funding: Funding,
     pub account: Account,
     pub ledger: Ledger
 }
 
 impl Bitfinex {
     pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Bitfinex::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
         Bitfinex {
-            book: Book::new(),
-            ticker: Ticker::new(),
-            trades: Trades::new(),
-            candles: Candles::new(),
-            orders: Orders::new(api_key.clone(), secret_key.clone()),
-            account: Account::new(api_key.clone(), secret_key.clone()),
-            ledger: Ledger::new(api_key.clone(), secret_key.clone()),
+            config: Config::with_config(config.clone()),
+            book: Book::with_config(config.clone()),
+            ticker: Ticker::with_config(config.clone()),
+            trades: Trades::with_config(config.clone()),
+            candles: Candles::with_config(config.clone()),
+            derivatives: Derivatives::with_config(config.clone()),
+            stats: Stats::with_config(config.clone()),
+            calc: Calc::with_config(config.clone()),
+            orders: Orders::with_config(api_key.clone(), secret_key.clone(), config.clone()),
+            positions: Positions::with_config(api_key.clone(), secret_key.clone(), config.clone()),
+            funding: Funding::with_config(api_key.clone(), secret_key.clone(), config.clone()),
+            account: Account::with_config(api_key.clone(), secret_key.clone(), config.clone()),
+            ledger: Ledger::with_config(api_key, secret_key, config),
         }
     }
 }
@@ -0,0 +1,352 @@
+use crate::account::{
+    auto_renew_status, invoice_status, movements_request, parse_summary, user_info,
+    AccountHistoryParams, AccountSummary, AuditEntry, CloseFundingReq, DepositAddressEntry, DepositAddressListReq, DepositAddressNotification, DepositAddressReq,
+    FundingAutoNotification, FundingAutoRenew, FundingAutoReq, FundingAutoStatusReq, FundingInfo,
+    FundingUpdateNotification, InvoiceInfo, InvoiceReq, InvoiceSearch, InvoiceStatus, KeepFundingReq, LnAddressReq, Login, MarginBase,
+    MarginSymbol, Movement, MovementHistoryParams, MovementPager, MovementReq, MovementResp, Permissions,
+    TransferReq, TransferResp, UserInfo, Wallet, WalletType, WithdrawReq, WithdrawResp, INVOICE_POLL_INTERVAL, LNX,
+};
+use crate::asynchronous::client::Client;
+use crate::asynchronous::config::Config;
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::symbol::{FundingCurrency, TradingPair};
+use log::*;
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+#[derive(Clone)]
+pub struct Account {
+    client: Client,
+}
+
+impl Account {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Account::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        Account::with_client(Client::with_config(api_key, secret_key, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Account { client }
+    }
+
+    pub async fn user_info(&self) -> Result<UserInfo> {
+        let data = self.client.post_signed("info/user".into(), "{}".to_string()).await?;
+
+        user_info(decode(data.as_str())?)
+    }
+
+    pub async fn summary(&self) -> Result<AccountSummary> {
+        let data = self.client.post_signed("summary".into(), "{}".to_string()).await?;
+
+        parse_summary(decode(data.as_str())?)
+    }
+
+    pub async fn login_history(&self, params: &AccountHistoryParams) -> Result<Vec<Login>> {
+        let payload: String = serde_json::to_string(params)?;
+        let data = self.client.post_signed("logins/hist".into(), payload).await?;
+
+        let logins: Vec<Login> = decode(data.as_str())?;
+
+        Ok(logins)
+    }
+
+    pub async fn permissions(&self) -> Result<Permissions> {
+        let data = self.client.post_signed("permissions".into(), "{}".to_string()).await?;
+
+        let permissions: Permissions = decode(data.as_str())?;
+
+        Ok(permissions)
+    }
+
+    pub async fn audit_history(&self, params: &AccountHistoryParams) -> Result<Vec<AuditEntry>> {
+        let payload: String = serde_json::to_string(params)?;
+        let data = self.client.post_signed("audit/hist".into(), payload).await?;
+
+        let audit: Vec<AuditEntry> = decode(data.as_str())?;
+
+        Ok(audit)
+    }
+
+    pub async fn get_wallets(&self) -> Result<Vec<Wallet>> {
+        let payload: String = "{}".to_string();
+        let data = self.client.post_signed("wallets".into(), payload).await?;
+        debug!("Wallets response: {}", data.as_str());
+        let wallets: Vec<Wallet> = decode(data.as_str())?;
+
+        Ok(wallets)
+    }
+
+    pub async fn margin_base(&self) -> Result<MarginBase> {
+        let payload: String = "{}".to_string();
+
+        let data = self
+            .client
+            .post_signed("info/margin/base".into(), payload)
+            .await?;
+
+        let margin: MarginBase = decode(data.as_str())?;
+
+        Ok(margin)
+    }
+
+    pub async fn margin_symbol<P>(&self, pair: P) -> Result<MarginSymbol>
+    where
+        P: TryInto<TradingPair>,
+        Error: From<P::Error>,
+    {
+        let payload: String = "{}".to_string();
+        let request: String = format!("info/margin/{}", pair.try_into()?);
+
+        let data = self.client.post_signed(request, payload).await?;
+
+        let margin: MarginSymbol = decode(data.as_str())?;
+
+        Ok(margin)
+    }
+
+    pub async fn funding_info<C>(&self, currency: C) -> Result<FundingInfo>
+    where
+        C: TryInto<FundingCurrency>,
+        Error: From<C::Error>,
+    {
+        let payload: String = "{}".to_string();
+        let request: String = format!("info/funding/{}", currency.try_into()?);
+
+        let data = self.client.post_signed(request, payload).await?;
+
+        let info: FundingInfo = decode(data.as_str())?;
+
+        Ok(info)
+    }
+
+    pub async fn funding_auto_renew_status<C>(&self, currency: C) -> Result<Option<FundingAutoRenew>>
+    where
+        C: TryInto<FundingCurrency>,
+        Error: From<C::Error>,
+    {
+        let currency = currency.try_into()?;
+        let payload: String = serde_json::to_string(&FundingAutoStatusReq { currency: currency.currency() })?;
+
+        let data = self.client.post_signed("funding/auto/status".into(), payload).await?;
+
+        auto_renew_status(decode(data.as_str())?)
+    }
+
+    pub async fn funding_auto_renew(&self, req: FundingAutoReq) -> Result<FundingAutoNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.funding_write("funding/auto", payload).await
+    }
+
+    pub async fn keep_funding(&self, req: KeepFundingReq) -> Result<FundingUpdateNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.funding_write("funding/keep", payload).await
+    }
+
+    pub async fn close_funding(&self, id: i64) -> Result<FundingUpdateNotification> {
+        let payload: String = serde_json::to_string(&CloseFundingReq { id })?;
+        self.funding_write("funding/close", payload).await
+    }
+
+    async fn funding_write<T: DeserializeOwned>(&self, request: &str, payload: String) -> Result<T> {
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request.into(), payload).await?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+
+    pub async fn generate_invoice_address(&self) -> Result<()> {
+        let req = LnAddressReq {
+            method: "LNX".to_owned(),
+            wallet: WalletType::Exchange,
+        };
+        let payload: String = serde_json::to_string(&req)?;
+        let request: String = "deposit/address".to_string();
+
+        debug!("Payload: {payload}");
+        self.client.post_w_signed(request, payload).await?;
+
+        Ok(())
+    }
+
+    pub async fn generate_invoice(&self, req: InvoiceReq) -> Result<InvoiceInfo> {
+        let payload: String = serde_json::to_string(&req)?;
+        let request: String = "deposit/invoice".to_string();
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request, payload).await?;
+
+        let info: InvoiceInfo = decode(data.as_str())?;
+
+        Ok(info)
+    }
+
+    /// Looks for the deposit paying `invoice` among the LNX movements since it was created.
+    pub async fn invoice_status(&self, invoice: &InvoiceInfo) -> Result<InvoiceStatus> {
+        let search = InvoiceSearch::new(invoice)?;
+        let mut pages = self.movements_pages(FundingCurrency::new(LNX), search.params.clone());
+
+        while let Some(page) = pages.next_page().await? {
+            if let Some(movement) = page.into_iter().find(|m| search.pays_invoice(m)) {
+                return Ok(invoice_status(movement));
+            }
+        }
+
+        Ok(InvoiceStatus::Unpaid)
+    }
+
+    /// Polls `invoice_status` until the invoice is paid or `timeout` elapses, returning
+    /// the last status seen. `Bolt11::expires_at` gives a sensible upper bound.
+    ///
+    /// Retryable errors are logged and the polling goes on; the error is only returned
+    /// when the last lookup before the deadline failed.
+    pub async fn wait_for_payment(&self, invoice: &InvoiceInfo, timeout: Duration) -> Result<InvoiceStatus> {
+        // Too far away to be represented: wait until the invoice is paid
+        let deadline = Instant::now().checked_add(timeout);
+
+        loop {
+            let status = match self.invoice_status(invoice).await {
+                Ok(status) if status.is_paid() => return Ok(status),
+                Ok(status) => Ok(status),
+                Err(e) if e.is_retryable() => {
+                    warn!("Invoice lookup failed, retrying: {}", e);
+                    Err(e)
+                }
+                Err(e) => return Err(e),
+            };
+
+            let now = Instant::now();
+            let wait = match deadline {
+                Some(deadline) if now >= deadline => return status,
+                Some(deadline) => INVOICE_POLL_INTERVAL.min(deadline - now),
+                None => INVOICE_POLL_INTERVAL,
+            };
+
+            sleep(wait).await;
+        }
+    }
+
+    pub async fn transfer(&self, req: TransferReq) -> Result<TransferResp> {
+        let payload: String = serde_json::to_string(&req)?;
+        let request: String = "transfer".to_string();
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request, payload).await?;
+        info!("Response: {}", data.as_str());
+        let info: TransferResp = decode(data.as_str())?;
+
+        Ok(info)
+    }
+
+    pub async fn withdraw(&self, req: WithdrawReq) -> Result<WithdrawResp> {
+        let payload: String = serde_json::to_string(&req)?;
+        let request: String = "withdraw".to_string();
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request, payload).await?;
+        info!("Response: {}", data.as_str());
+        let info: WithdrawResp = decode(data.as_str())?;
+
+        Ok(info)
+    }
+
+    pub async fn movement_info(&self, req: MovementReq) -> Result<MovementResp> {
+        let payload: String = serde_json::to_string(&req)?;
+        let request: String = "movements/info".to_string();
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_signed(request, payload).await?;
+        info!("Response: {}", data.as_str());
+        let info: MovementResp = decode(data.as_str())?;
+
+        Ok(info)
+    }
+
+    /// Deposits and withdrawals of a currency, or of every currency with `None`.
+    pub async fn movements<C>(&self, currency: C, params: &MovementHistoryParams) -> Result<Vec<Movement>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        let payload: String = serde_json::to_string(params)?;
+        let data = self.client.post_signed(movements_request(currency.into()), payload).await?;
+
+        let movements: Vec<Movement> = decode(data.as_str())?;
+
+        Ok(movements)
+    }
+
+    pub fn movements_pages<C>(&self, currency: C, params: MovementHistoryParams) -> MovementHistoryPages
+        where C: Into<Option<FundingCurrency>>
+    {
+        MovementHistoryPages { client: self.client.clone(), pager: MovementPager::new(currency.into(), params) }
+    }
+
+    pub async fn deposit_address(&self, req: DepositAddressReq) -> Result<DepositAddressNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed("deposit/address".into(), payload).await?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+
+    /// Deposit address of a currency, resolving its method with `Config::tx_method`.
+    /// Currencies on several networks fail there, use `deposit_address` with a method.
+    pub async fn currency_deposit_address<C>(&self, currency: C, wallet: WalletType, renew: bool) -> Result<DepositAddressNotification>
+        where C: TryInto<FundingCurrency>, Error: From<C::Error>
+    {
+        let currency = currency.try_into()?;
+        let method = Config::with_client(self.client.clone())
+            .tx_method(currency.currency()).await?
+            .ok_or_else(|| Error::InvalidRequest(format!("no deposit method for {}", currency.currency())))?;
+
+        let req = DepositAddressReq::new(method, wallet);
+        self.deposit_address(if renew { req.renew() } else { req }).await
+    }
+
+    /// Addresses created for a deposit method.
+    pub async fn deposit_addresses(&self, method: &str) -> Result<Vec<DepositAddressEntry>> {
+        let payload: String = serde_json::to_string(&DepositAddressListReq { method })?;
+        let data = self.client.post_signed("deposit/address/list".into(), payload).await?;
+
+        let addresses: Vec<DepositAddressEntry> = decode(data.as_str())?;
+
+        Ok(addresses)
+    }
+}
+
+/// Async counterpart of `account::MovementHistoryIter`, yielding a page at a time.
+pub struct MovementHistoryPages {
+    client: Client,
+    pager: MovementPager,
+}
+
+impl MovementHistoryPages {
+    /// Next page of movements not returned yet, `None` once the range is exhausted.
+    /// A page can be empty when a crowded millisecond has to be requested on its own.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<Movement>>> {
+        let (request, payload) = match self.pager.next_request()? {
+            Some(request) => request,
+            None => return Ok(None),
+        };
+
+        let page = match self.client.post_signed(request, payload).await {
+            Ok(data) => decode::<Vec<Movement>>(data.as_str()).and_then(|page| self.pager.advance(page)),
+            Err(e) => Err(e),
+        };
+
+        match page {
+            Ok(page) => Ok(Some(page)),
+            Err(e) => {
+                self.pager.finish();
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::asynchronous::*;
+use crate::client::ClientConfig;
+
+#[derive(Clone)]
+pub struct Bitfinex {
+    pub config: Config,
+    pub book: Book,
+    pub ticker: Ticker,
+    pub trades: Trades,
+    pub candles: Candles,
+    pub derivatives: Derivatives,
+    pub stats: Stats,
+    pub calc: Calc,
+    pub orders: Orders,
+    pub positions: Positions,
+    pub funding: Funding,
+    pub account: Account,
+    pub ledger: Ledger
+}
+
+impl Bitfinex {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Bitfinex::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        Bitfinex::with_client(Client::with_config(api_key, secret_key, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Bitfinex {
+            config: Config::with_client(client.clone()),
+            book: Book::with_client(client.clone()),
+            ticker: Ticker::with_client(client.clone()),
+            trades: Trades::with_client(client.clone()),
+            candles: Candles::with_client(client.clone()),
+            derivatives: Derivatives::with_client(client.clone()),
+            stats: Stats::with_client(client.clone()),
+            calc: Calc::with_client(client.clone()),
+            orders: Orders::with_client(client.clone()),
+            positions: Positions::with_client(client.clone()),
+            funding: Funding::with_client(client.clone()),
+            account: Account::with_client(client.clone()),
+            ledger: Ledger::with_client(client),
+        }
+    }
+}
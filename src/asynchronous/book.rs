@@ -0,0 +1,75 @@
+use crate::asynchronous::client::Client;
+use crate::book::{book_endpoint, BookParams, FundingCurrency, RawBook, RawFundingBook, TradingPair};
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::precision::Precision;
+use crate::symbol::{self, Symbol};
+
+#[derive(Clone)]
+pub struct Book {
+    client: Client,
+}
+
+impl Default for Book {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Book {
+    pub fn new() -> Self {
+        Book::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Book::with_client(Client::with_config(None, None, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Book { client }
+    }
+
+    pub async fn funding_currency<C>(&self, currency: C, precision: Precision, params: &BookParams) -> Result<Vec<FundingCurrency>>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
+    {
+        let endpoint = book_endpoint(Symbol::Funding(currency.try_into()?), precision)?;
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let book: Vec<FundingCurrency> = decode(data.as_str())?;
+
+        Ok(book)
+    }
+
+    pub async fn trading_pair<P>(&self, pair: P, precision: Precision, params: &BookParams) -> Result<Vec<TradingPair>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
+    {
+        let endpoint = book_endpoint(Symbol::Trading(pair.try_into()?), precision)?;
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let book: Vec<TradingPair> = decode(data.as_str())?;
+
+        Ok(book)
+    }
+
+    pub async fn raw_funding_currency<C>(&self, currency: C, params: &BookParams) -> Result<Vec<RawFundingBook>>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
+    {
+        let endpoint: String = format!("book/{}/{}", currency.try_into()?, Precision::R0);
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let book: Vec<RawFundingBook> = decode(data.as_str())?;
+
+        Ok(book)
+    }
+
+    pub async fn raw_trading_pair<P>(&self, pair: P, params: &BookParams) -> Result<Vec<RawBook>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
+    {
+        let endpoint: String = format!("book/{}/{}", pair.try_into()?, Precision::R0);
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let book: Vec<RawBook> = decode(data.as_str())?;
+
+        Ok(book)
+    }
+}
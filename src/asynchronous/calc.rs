@@ -0,0 +1,60 @@
+use crate::asynchronous::client::Client;
+use crate::calc::{fx_payload, fx_rate, FundingAvg, FundingAvgParams, TradeAvg, TradeAvgParams};
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::number::Number;
+use crate::symbol::{FundingCurrency, TradingPair};
+
+#[derive(Clone)]
+pub struct Calc {
+    client: Client,
+}
+
+impl Default for Calc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Calc {
+    pub fn new() -> Self {
+        Calc::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Calc::with_client(Client::with_config(None, None, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Calc { client }
+    }
+
+    pub async fn trade_avg<P>(&self, pair: P, params: &TradeAvgParams) -> Result<TradeAvg>
+        where P: TryInto<TradingPair>, Error: From<P::Error>
+    {
+        let query = params.to_query(&pair.try_into()?);
+        let data = self.client.post("calc/trade/avg".into(), query, String::new()).await?;
+
+        let avg: TradeAvg = decode(data.as_str())?;
+
+        Ok(avg)
+    }
+
+    pub async fn funding_avg<C>(&self, currency: C, params: &FundingAvgParams) -> Result<FundingAvg>
+        where C: TryInto<FundingCurrency>, Error: From<C::Error>
+    {
+        let query = params.to_query(&currency.try_into()?);
+        let data = self.client.post("calc/trade/avg".into(), query, String::new()).await?;
+
+        let avg: FundingAvg = decode(data.as_str())?;
+
+        Ok(avg)
+    }
+
+    pub async fn fx(&self, from: &str, to: &str) -> Result<Number> {
+        let payload = fx_payload(from, to)?;
+        let data = self.client.post("calc/fx".into(), String::new(), payload).await?;
+
+        fx_rate(decode(data.as_str())?)
+    }
+}
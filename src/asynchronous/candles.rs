@@ -0,0 +1,47 @@
+use crate::asynchronous::client::Client;
+use crate::candles::{Candle, CandleHistoryParams, CandleKey};
+use crate::client::ClientConfig;
+use crate::errors::*;
+
+#[derive(Clone)]
+pub struct Candles {
+    client: Client,
+}
+
+impl Default for Candles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Candles {
+    pub fn new() -> Self {
+        Candles::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Candles::with_client(Client::with_config(None, None, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Candles { client }
+    }
+
+    pub async fn last(&self, key: &CandleKey) -> Result<Candle> {
+        let endpoint: String = format!("candles/{}/last", key);
+        let data = self.client.get(endpoint, String::new()).await?;
+
+        let history: Candle = decode(data.as_str())?;
+
+        Ok(history)
+    }
+
+    pub async fn history(&self, key: &CandleKey, params: &CandleHistoryParams) -> Result<Vec<Candle>> {
+        let endpoint: String = format!("candles/{}/hist", key);
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let history: Vec<Candle> = decode(data.as_str())?;
+
+        Ok(history)
+    }
+}
@@ -0,0 +1,171 @@
+use crate::errors::*;
+use crate::client::{
+    handle_response, public_url, signed_headers, ClientConfig, API_SIGNATURE_PATH,
+    API_SIGNATURE_PATH_W, NO_PARAMS,
+};
+use reqwest;
+use reqwest::Response;
+use reqwest::header::{HeaderValue, CONTENT_TYPE, USER_AGENT};
+use crate::rate_limit::endpoint_family;
+use serde::Serialize;
+use std::future::Future;
+use tokio::time::sleep;
+use log::*;
+
+/// Async REST client. Cloning is cheap and clones share the underlying connection pool.
+#[derive(Clone)]
+pub struct Client {
+    api_key: String,
+    secret_key: String,
+    config: ClientConfig,
+    http: reqwest::Client,
+}
+
+impl Client {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Client::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        let mut builder = reqwest::Client::builder();
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let http = builder.build().expect("Failed to build the HTTP client");
+
+        Client {
+            api_key : api_key.unwrap_or_default(),
+            secret_key : secret_key.unwrap_or_default(),
+            config,
+            http,
+        }
+    }
+
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    pub async fn get(&self, endpoint: String, request: String) -> Result<String> {
+        let url = public_url(&self.config, &endpoint, &request);
+
+        self.execute(&endpoint_family("", &endpoint), false, || self.send_get(&url)).await
+    }
+
+    pub async fn post(&self, endpoint: String, request: String, payload: String) -> Result<String> {
+        let url = public_url(&self.config, &endpoint, &request);
+
+        self.execute(&endpoint_family("", &endpoint), false, || self.send_post(&url, &payload)).await
+    }
+
+    pub async fn post_signed(&self, request: String, payload: String) -> Result<String> {
+        self.post_signed_params(request, payload, NO_PARAMS).await
+    }
+
+    pub async fn post_signed_params<P: Serialize + Sync + ?Sized>(
+        &self,
+        request: String,
+        payload: String,
+        params: &P,
+    ) -> Result<String> {
+        let url: String = format!("{}auth/r/{}", self.config.rest_host, request);
+
+        self.execute(&endpoint_family("auth/r/", &request), false, || {
+            self.send_signed(&url, API_SIGNATURE_PATH, &request, &payload, params)
+        }).await
+    }
+
+    pub async fn post_w_signed(&self, request: String, payload: String) -> Result<String> {
+        self.post_w_signed_params(request, payload, NO_PARAMS).await
+    }
+
+    pub async fn post_w_signed_params<P: Serialize + Sync + ?Sized>(
+        &self,
+        request: String,
+        payload: String,
+        params: &P,
+    ) -> Result<String> {
+        let url: String = format!("{}auth/w/{}", self.config.rest_host, request);
+
+        self.execute(&endpoint_family("auth/w/", &request), true, || {
+            self.send_signed(&url, API_SIGNATURE_PATH_W, &request, &payload, params)
+        }).await
+    }
+
+    async fn send_get(&self, url: &str) -> Result<String> {
+        let response = self.http.get(url)
+            .header(USER_AGENT, HeaderValue::from_str(self.config.user_agent.as_str())?)
+            .send()
+            .await?;
+
+        self.handler(response).await
+    }
+
+    async fn send_post(&self, url: &str, payload: &str) -> Result<String> {
+        let response = self.http.post(url)
+            .header(USER_AGENT, HeaderValue::from_str(self.config.user_agent.as_str())?)
+            .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+            .body(payload.to_owned())
+            .send()
+            .await?;
+
+        self.handler(response).await
+    }
+
+    async fn send_signed<P: Serialize + Sync + ?Sized>(
+        &self,
+        url: &str,
+        signature_prefix: &str,
+        request: &str,
+        payload: &str,
+        params: &P,
+    ) -> Result<String> {
+        let headers = signed_headers(&self.api_key, &self.secret_key, &self.config, signature_prefix, request, payload)?;
+
+        trace!("URL: {}", url);
+        trace!("Headers: {:?}", headers);
+        let response = self.http.post(url)
+            .headers(headers)
+            .body(payload.to_owned())
+            .query(params)
+            .send()
+            .await?;
+        trace!("Response: {response:?}");
+        self.handler(response).await
+    }
+
+    /// Async counterpart of the blocking client's retry and rate limit loop.
+    async fn execute<F, Fut>(&self, family: &str, write: bool, send: F) -> Result<String>
+        where F: Fn() -> Fut, Fut: Future<Output = Result<String>>
+    {
+        let mut attempt = 0;
+        loop {
+            if let Some(ref limiter) = self.config.rate_limiter {
+                let wait = limiter.reserve(family);
+                if !wait.is_zero() {
+                    debug!("Rate limiter: waiting {:?} for {}", wait, family);
+                    sleep(wait).await;
+                }
+            }
+
+            match send().await {
+                Err(e) => match self.config.retry_policy.retry_delay(&e, attempt, write) {
+                    Some(delay) => {
+                        warn!("Request to {} failed ({}), retrying in {:?}", family, e, delay);
+                        sleep(delay).await;
+                        attempt += 1;
+                    }
+                    None => return Err(e),
+                },
+                result => return result,
+            }
+        }
+    }
+
+    async fn handler(&self, response: Response) -> Result<String> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+
+        handle_response(status, &headers, body)
+    }
+}
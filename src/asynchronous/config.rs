@@ -0,0 +1,103 @@
+use crate::asynchronous::client::Client;
+use crate::client::ClientConfig;
+use crate::config::{
+    fee_tiers, find_tx_method, first, parse_entries, platform_status, to_map, CurrencyMap, FeeTier, PairInfo,
+    PlatformStatus, WithdrawalFee, FEES, TX_METHODS,
+};
+use crate::errors::*;
+use crate::symbol::TradingPair;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct Config {
+    client: Client,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Config::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Config::with_client(Client::with_config(None, None, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Config { client }
+    }
+
+    pub async fn platform_status(&self) -> Result<PlatformStatus> {
+        let data = self.client.get("platform/status".into(), String::new()).await?;
+
+        platform_status(decode(data.as_str())?)
+    }
+
+    pub async fn exchange_pairs(&self) -> Result<Vec<TradingPair>> {
+        self.conf_list("pub:list:pair:exchange").await
+    }
+
+    pub async fn margin_pairs(&self) -> Result<Vec<TradingPair>> {
+        self.conf_list("pub:list:pair:margin").await
+    }
+
+    pub async fn futures_pairs(&self) -> Result<Vec<TradingPair>> {
+        self.conf_list("pub:list:pair:futures").await
+    }
+
+    pub async fn currencies(&self) -> Result<Vec<String>> {
+        self.conf_list("pub:list:currency").await
+    }
+
+    pub async fn currency_labels(&self) -> Result<HashMap<String, String>> {
+        self.currency_map(CurrencyMap::Label).await
+    }
+
+    pub async fn currency_symbols(&self) -> Result<HashMap<String, String>> {
+        self.currency_map(CurrencyMap::Symbol).await
+    }
+
+    pub async fn withdrawal_fees(&self) -> Result<HashMap<String, WithdrawalFee>> {
+        self.currency_map(CurrencyMap::TxFee).await
+    }
+
+    pub async fn currency_map<T: DeserializeOwned>(&self, map: CurrencyMap) -> Result<HashMap<String, T>> {
+        self.conf_list::<(String, T)>(map.key()).await.map(to_map)
+    }
+
+    pub async fn pair_info(&self) -> Result<HashMap<TradingPair, PairInfo>> {
+        self.conf_list::<(TradingPair, PairInfo)>("pub:info:pair").await.map(to_map)
+    }
+
+    pub async fn futures_pair_info(&self) -> Result<HashMap<TradingPair, PairInfo>> {
+        self.conf_list::<(TradingPair, PairInfo)>("pub:info:pair:futures").await.map(to_map)
+    }
+
+    pub async fn tx_methods(&self) -> Result<HashMap<String, Vec<String>>> {
+        self.conf_list::<(String, Vec<String>)>(TX_METHODS).await.map(to_map)
+    }
+
+    pub async fn tx_method(&self, currency: &str) -> Result<Option<String>> {
+        find_tx_method(self.conf_list(TX_METHODS).await?, currency)
+    }
+
+    pub async fn fees(&self) -> Result<Vec<FeeTier>> {
+        self.conf(FEES).await.map(fee_tiers)
+    }
+
+    pub async fn conf_list<T: DeserializeOwned>(&self, key: &str) -> Result<Vec<T>> {
+        self.conf(key).await.map(|entries| parse_entries(key, entries))
+    }
+
+    pub async fn conf<T: DeserializeOwned>(&self, key: &str) -> Result<T> {
+        let data = self.client.get(format!("conf/{}", key), String::new()).await?;
+
+        first(key, decode(data.as_str())?)
+    }
+}
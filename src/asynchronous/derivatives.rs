@@ -0,0 +1,56 @@
+use crate::asynchronous::client::Client;
+use crate::client::ClientConfig;
+use crate::derivatives::{parse_status, status_query, DerivStatus, Liquidation, StatusHistoryParams};
+use crate::errors::*;
+use crate::symbol::TradingPair;
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct Derivatives {
+    client: Client,
+}
+
+impl Default for Derivatives {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Derivatives {
+    pub fn new() -> Self {
+        Derivatives::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Derivatives::with_client(Client::with_config(None, None, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Derivatives { client }
+    }
+
+    pub async fn status(&self, pairs: &[TradingPair]) -> Result<HashMap<TradingPair, DerivStatus>> {
+        let data = self.client.get("status/deriv".into(), status_query(pairs)).await?;
+
+        parse_status(decode(data.as_str())?)
+    }
+
+    pub async fn status_history<P>(&self, pair: P, params: &StatusHistoryParams) -> Result<Vec<DerivStatus>>
+        where P: TryInto<TradingPair>, Error: From<P::Error>
+    {
+        let endpoint: String = format!("status/deriv/{}/hist", pair.try_into()?);
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let history: Vec<DerivStatus> = decode(data.as_str())?;
+
+        Ok(history)
+    }
+
+    pub async fn liquidations(&self, params: &StatusHistoryParams) -> Result<Vec<Liquidation>> {
+        let data = self.client.get("liquidations/hist".into(), params.to_query()).await?;
+
+        let liquidations: Vec<Vec<Liquidation>> = decode(data.as_str())?;
+
+        Ok(liquidations.into_iter().flatten().collect())
+    }
+}
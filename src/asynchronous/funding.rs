@@ -0,0 +1,106 @@
+use crate::asynchronous::client::Client;
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::funding::*;
+use crate::symbol::FundingCurrency;
+use serde::de::DeserializeOwned;
+use log::*;
+
+#[derive(Clone)]
+pub struct Funding {
+    client: Client,
+}
+
+impl Funding {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Funding::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        Funding::with_client(Client::with_config(api_key, secret_key, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Funding { client }
+    }
+
+    /// Active offers of a currency, or of every currency with `None`.
+    pub async fn offers<C>(&self, currency: C) -> Result<Vec<FundingOffer>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("offers", currency.into(), false), "{}".to_string()).await
+    }
+
+    pub async fn offer_history<C>(&self, currency: C, params: &FundingHistoryParams) -> Result<Vec<FundingOffer>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("offers", currency.into(), true), serde_json::to_string(params)?).await
+    }
+
+    pub async fn submit_offer(&self, req: SubmitFundingOffer) -> Result<FundingOfferNotification> {
+        req.validate()?;
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("funding/offer/submit", payload).await
+    }
+
+    pub async fn cancel_offer(&self, id: i64) -> Result<FundingOfferNotification> {
+        let payload: String = serde_json::to_string(&CancelFundingOffer { id })?;
+        self.write("funding/offer/cancel", payload).await
+    }
+
+    /// Cancels the offers of a currency, or every offer with `None`.
+    pub async fn cancel_all_offers<C>(&self, currency: C) -> Result<FundingCancelAllNotification>
+        where C: Into<Option<FundingCurrency>>
+    {
+        let currency = currency.into().map(|c| c.currency().to_owned());
+        let payload: String = serde_json::to_string(&CancelAllFundingOffers { currency })?;
+        self.write("funding/offer/cancel/all", payload).await
+    }
+
+    /// Funds lent and not used in a position.
+    pub async fn loans<C>(&self, currency: C) -> Result<Vec<FundingLoan>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("loans", currency.into(), false), "{}".to_string()).await
+    }
+
+    pub async fn loan_history<C>(&self, currency: C, params: &FundingHistoryParams) -> Result<Vec<FundingLoan>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("loans", currency.into(), true), serde_json::to_string(params)?).await
+    }
+
+    /// Funds used in positions.
+    pub async fn credits<C>(&self, currency: C) -> Result<Vec<FundingCredit>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("credits", currency.into(), false), "{}".to_string()).await
+    }
+
+    pub async fn credit_history<C>(&self, currency: C, params: &FundingHistoryParams) -> Result<Vec<FundingCredit>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("credits", currency.into(), true), serde_json::to_string(params)?).await
+    }
+
+    pub async fn trades<C>(&self, currency: C, params: &FundingHistoryParams) -> Result<Vec<FundingTrade>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("trades", currency.into(), true), serde_json::to_string(params)?).await
+    }
+
+    async fn read<T: DeserializeOwned>(&self, request: String, payload: String) -> Result<T> {
+        let data = self.client.post_signed(request, payload).await?;
+
+        decode(data.as_str())
+    }
+
+    async fn write<T: DeserializeOwned>(&self, request: &str, payload: String) -> Result<T> {
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request.into(), payload).await?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+}
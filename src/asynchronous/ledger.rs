@@ -0,0 +1,48 @@
+use crate::asynchronous::client::Client;
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::ledger::{Entry, HistoryParams};
+
+#[derive(Clone)]
+pub struct Ledger {
+    client: Client,
+}
+
+impl Ledger {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Ledger::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        Ledger::with_client(Client::with_config(api_key, secret_key, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Ledger { client }
+    }
+
+    pub async fn get_history<S>(
+        &self,
+        symbol: S,
+        start: u128,
+        end: u128,
+        limit: i32,
+    ) -> Result<Vec<Entry>>
+    where
+        S: Into<String>,
+    {
+        let payload: String = "{}".to_string();
+        let request: String = format!("ledgers/{}/hist", symbol.into());
+        let params = HistoryParams{
+            start: format!("{}", start),
+            end: format!("{}", end),
+            limit,
+        };
+
+        let data = self.client.post_signed_params(request, payload, &params).await?;
+
+        let entry: Vec<Entry> = decode(data.as_str())?;
+
+        Ok(entry)
+    }
+}
@@ -0,0 +1,41 @@
+//! Non-blocking versions of the REST sub-APIs, available with the `async` cargo feature.
+//!
+//! Every sub-API created through [`Bitfinex`] shares one [`Client`] and therefore one
+//! connection pool. Response types are the same as in the blocking modules.
+//!
+//! [`WebSockets`] is a tokio-tungstenite client yielding an [`EventStream`] of
+//! `events::WsEvent` values.
+
+mod client;
+mod config;
+mod book;
+mod ticker;
+mod trades;
+mod candles;
+mod derivatives;
+mod stats;
+mod calc;
+mod orders;
+mod positions;
+mod funding;
+mod account;
+mod ledger;
+mod api;
+mod websockets;
+
+pub use self::client::Client;
+pub use self::config::Config;
+pub use self::book::Book;
+pub use self::ticker::Ticker;
+pub use self::trades::{TradeHistoryPages, Trades};
+pub use self::candles::Candles;
+pub use self::derivatives::Derivatives;
+pub use self::stats::Stats;
+pub use self::calc::Calc;
+pub use self::orders::Orders;
+pub use self::positions::Positions;
+pub use self::funding::Funding;
+pub use self::account::{Account, MovementHistoryPages};
+pub use self::ledger::Ledger;
+pub use self::api::Bitfinex;
+pub use self::websockets::{EventStream, WebSockets};
@@ -0,0 +1,103 @@
+use crate::asynchronous::client::Client;
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::symbol::TradingPair;
+use crate::orders::*;
+use serde::de::DeserializeOwned;
+use log::*;
+
+#[derive(Clone)]
+pub struct Orders {
+    client: Client,
+}
+
+impl Orders {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Orders::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        Orders::with_client(Client::with_config(api_key, secret_key, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Orders { client }
+    }
+
+    pub async fn active_orders(&self) -> Result<Vec<Order>> {
+        let payload: String = "{}".to_string();
+
+        self.orders("orders".to_owned(), payload).await
+    }
+
+    pub async fn history<T>(&self, pair: T) -> Result<Vec<Order>>
+        where T: Into<Option<TradingPair>>
+    {
+        let payload: String = "{}".to_string();
+
+        match pair.into() {
+            Some(pair) => self.orders(format!("orders/{}/hist", pair), payload).await,
+            None => self.orders("orders/hist".into(), payload).await,
+        }
+    }
+
+    pub async fn orders<S>(&self, request: S, payload: S) -> Result<Vec<Order>>
+        where S: Into<String>
+    {
+        let data = self.client.post_signed(request.into(), payload.into()).await?;
+
+        let orders: Vec<Order> = decode(data.as_str())?;
+
+        Ok(orders)
+    }
+
+    pub async fn submit(&self, req: SubmitOrder) -> Result<SubmitOrderResp> {
+        req.validate()?;
+        let payload: String = serde_json::to_string(&req)?;
+        let request: String = "order/submit".to_string();
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request, payload).await?;
+        info!("Response: {data}");
+
+        let info: SubmitOrderResp = decode(data.as_str())?;
+
+        Ok(info)
+    }
+
+    pub async fn cancel(&self, req: CancelOrder) -> Result<OrderNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("order/cancel", payload).await
+    }
+
+    pub async fn cancel_multi(&self, req: CancelOrders) -> Result<OrdersNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("order/cancel/multi", payload).await
+    }
+
+    pub async fn cancel_all(&self) -> Result<OrdersNotification> {
+        self.cancel_multi(CancelOrders::all()).await
+    }
+
+    pub async fn update(&self, req: UpdateOrder) -> Result<OrderNotification> {
+        req.validate()?;
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("order/update", payload).await
+    }
+
+    /// Sends up to 75 submit, update and cancel operations in one request.
+    pub async fn multi(&self, ops: &[OrderOp]) -> Result<OrderMultiResp> {
+        validate_multi(ops)?;
+        let payload: String = serde_json::to_string(&OrderMultiReq { ops })?;
+        self.write("order/multi", payload).await
+    }
+
+    async fn write<T: DeserializeOwned>(&self, request: &str, payload: String) -> Result<T> {
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request.into(), payload).await?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+}
@@ -0,0 +1,77 @@
+use crate::asynchronous::client::Client;
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::positions::*;
+use serde::de::DeserializeOwned;
+use log::*;
+
+#[derive(Clone)]
+pub struct Positions {
+    client: Client,
+}
+
+impl Positions {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Positions::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        Positions::with_client(Client::with_config(api_key, secret_key, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Positions { client }
+    }
+
+    pub async fn active_positions(&self) -> Result<Vec<Position>> {
+        self.positions("positions", "{}".to_string()).await
+    }
+
+    pub async fn history(&self, params: &PositionHistoryParams) -> Result<Vec<Position>> {
+        self.positions("positions/hist", serde_json::to_string(params)?).await
+    }
+
+    pub async fn snapshots(&self, params: &PositionHistoryParams) -> Result<Vec<Position>> {
+        self.positions("positions/snap", serde_json::to_string(params)?).await
+    }
+
+    pub async fn audit(&self, ids: &[i64], params: &PositionHistoryParams) -> Result<Vec<Position>> {
+        self.positions("positions/audit", serde_json::to_string(&PositionAuditReq { ids, params })?).await
+    }
+
+    pub async fn claim(&self, req: ClaimPosition) -> Result<PositionNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("position/claim", payload).await
+    }
+
+    pub async fn increase(&self, req: IncreasePosition) -> Result<PositionIncreaseNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("position/increase", payload).await
+    }
+
+    pub async fn increase_info(&self, req: &IncreasePosition) -> Result<PositionIncreaseInfo> {
+        let payload: String = serde_json::to_string(req)?;
+        let data = self.client.post_signed("position/increase/info".into(), payload).await?;
+
+        let info: PositionIncreaseInfo = decode(data.as_str())?;
+
+        Ok(info)
+    }
+
+    async fn positions(&self, request: &str, payload: String) -> Result<Vec<Position>> {
+        let data = self.client.post_signed(request.into(), payload).await?;
+
+        let positions: Vec<Position> = decode(data.as_str())?;
+
+        Ok(positions)
+    }
+
+    async fn write<T: DeserializeOwned>(&self, request: &str, payload: String) -> Result<T> {
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request.into(), payload).await?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::asynchronous::client::Client;
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::stats::{
+    rankings_endpoint, stats_endpoint, Ranking, RankingKey, RankingPeriod, Section, StatsHistoryParams, StatsKey,
+    StatsValue,
+};
+use crate::symbol::TradingPair;
+
+#[derive(Clone)]
+pub struct Stats {
+    client: Client,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Stats::with_client(Client::with_config(None, None, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Stats { client }
+    }
+
+    pub async fn last(&self, key: &StatsKey) -> Result<StatsValue> {
+        let endpoint = stats_endpoint(key, Section::Last);
+        let data = self.client.get(endpoint, String::new()).await?;
+
+        let value: StatsValue = decode(data.as_str())?;
+
+        Ok(value)
+    }
+
+    pub async fn history(&self, key: &StatsKey, params: &StatsHistoryParams) -> Result<Vec<StatsValue>> {
+        let endpoint = stats_endpoint(key, Section::Hist);
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let values: Vec<StatsValue> = decode(data.as_str())?;
+
+        Ok(values)
+    }
+
+    pub async fn rankings<P>(
+        &self,
+        key: RankingKey,
+        period: RankingPeriod,
+        pair: P,
+        params: &StatsHistoryParams,
+    ) -> Result<Vec<Ranking>>
+        where P: TryInto<TradingPair>, Error: From<P::Error>
+    {
+        let endpoint = rankings_endpoint(key, period, &pair.try_into()?);
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let rankings: Vec<Ranking> = decode(data.as_str())?;
+
+        Ok(rankings)
+    }
+}
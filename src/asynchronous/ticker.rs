@@ -0,0 +1,82 @@
+use crate::asynchronous::client::Client;
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::symbol::{self, Symbol};
+use crate::ticker::{
+    history_query, parse_tickers, symbols_query, FundingCurrency, ALL_SYMBOLS, TickerData, TickerHistory,
+    TickersHistoryParams, TradingPair,
+};
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct Ticker {
+    client: Client,
+}
+
+impl Default for Ticker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ticker {
+    pub fn new() -> Self {
+        Ticker::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Ticker::with_client(Client::with_config(None, None, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Ticker { client }
+    }
+
+    pub async fn funding_currency<C>(&self, currency: C) -> Result<FundingCurrency>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
+    {
+        let endpoint: String = format!("ticker/{}", currency.try_into()?);
+        let data = self.client.get(endpoint, String::new()).await?;
+
+        let ticker: FundingCurrency = decode(data.as_str())?;
+
+        Ok(ticker)
+    }
+
+    pub async fn trading_pair<P>(&self, pair: P) -> Result<TradingPair>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
+    {
+        let endpoint: String = format!("ticker/{}", pair.try_into()?);
+        let data = self.client.get(endpoint, String::new()).await?;
+
+        let ticker: TradingPair = decode(data.as_str())?;
+
+        Ok(ticker)
+    }
+
+    pub async fn tickers(&self, symbols: &[Symbol]) -> Result<HashMap<Symbol, TickerData>> {
+        self.fetch_tickers(&symbols_query(symbols)).await
+    }
+
+    pub async fn all_tickers(&self) -> Result<HashMap<Symbol, TickerData>> {
+        self.fetch_tickers(ALL_SYMBOLS).await
+    }
+
+    async fn fetch_tickers(&self, symbols: &str) -> Result<HashMap<Symbol, TickerData>> {
+        let data = self.client.get("tickers".into(), format!("symbols={}", symbols)).await?;
+
+        parse_tickers(decode(data.as_str())?)
+    }
+
+    pub async fn history(
+        &self,
+        pairs: &[symbol::TradingPair],
+        params: &TickersHistoryParams,
+    ) -> Result<Vec<TickerHistory>> {
+        let data = self.client.get("tickers/hist".into(), history_query(pairs, params)).await?;
+
+        let history: Vec<TickerHistory> = decode(data.as_str())?;
+
+        Ok(history)
+    }
+}
@@ -0,0 +1,134 @@
+use crate::asynchronous::client::Client;
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::symbol;
+use crate::trades::{
+    FundingCurrency, HistoryEntry, Trade, TradeHistoryParams, TradePager, TradingPair,
+};
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+#[derive(Clone)]
+pub struct Trades {
+    client: Client,
+}
+
+impl Default for Trades {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Trades {
+    pub fn new() -> Self {
+        Trades::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Trades::with_client(Client::with_config(None, None, config))
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Trades { client }
+    }
+
+    pub async fn funding_currency<C>(&self, currency: C, params: &TradeHistoryParams) -> Result<Vec<FundingCurrency>>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
+    {
+        let endpoint: String = format!("trades/{}/hist", currency.try_into()?);
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let trades: Vec<FundingCurrency> = decode(data.as_str())?;
+
+        Ok(trades)
+    }
+
+    pub async fn trading_pair<P>(&self, pair: P, params: &TradeHistoryParams) -> Result<Vec<TradingPair>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
+    {
+        let endpoint: String = format!("trades/{}/hist", pair.try_into()?);
+        let data = self.client.get(endpoint, params.to_query()).await?;
+
+        let trades: Vec<TradingPair> = decode(data.as_str())?;
+
+        Ok(trades)
+    }
+
+    /// Pages through every trade in the range, see `trades::Trades::trading_pair_iter`.
+    pub fn trading_pair_pages<P>(&self, pair: P, params: TradeHistoryParams) -> Result<TradeHistoryPages<TradingPair>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
+    {
+        let endpoint: String = format!("trades/{}/hist", pair.try_into()?);
+        Ok(TradeHistoryPages::new(self.client.clone(), endpoint, params))
+    }
+
+    pub fn funding_currency_pages<C>(&self, currency: C, params: TradeHistoryParams) -> Result<TradeHistoryPages<FundingCurrency>>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
+    {
+        let endpoint: String = format!("trades/{}/hist", currency.try_into()?);
+        Ok(TradeHistoryPages::new(self.client.clone(), endpoint, params))
+    }
+
+    pub async fn history<P>(&self, pair: P) -> Result<Vec<Trade>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
+    {
+        let payload: String = "{}".to_string();
+
+        let request: String = format!("trades/{}/hist", pair.try_into()?);
+        self.trades(request, payload).await
+    }
+
+    pub async fn generated_by_order<P, S>(&self, pair: P, order_id: S) -> Result<Vec<Trade>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>, S: Into<String>
+    {
+        let payload: String = "{}".to_string();
+
+        let request: String = format!("order/{}:{}/trades", pair.try_into()?, order_id.into());
+        self.trades(request, payload).await
+    }
+
+    pub async fn trades<S>(&self, request: S, payload: S) -> Result<Vec<Trade>>
+        where S: Into<String>
+    {
+        let data = self.client.post_signed(request.into(), payload.into()).await?;
+
+        let orders: Vec<Trade> = decode(data.as_str())?;
+
+        Ok(orders)
+    }
+}
+
+/// Async counterpart of `trades::TradeHistoryIter`, yielding a page at a time.
+pub struct TradeHistoryPages<T> {
+    client: Client,
+    pager: TradePager,
+    _trade: PhantomData<T>,
+}
+
+impl<T: HistoryEntry + DeserializeOwned> TradeHistoryPages<T> {
+    fn new(client: Client, endpoint: String, params: TradeHistoryParams) -> Self {
+        TradeHistoryPages { client, pager: TradePager::new(endpoint, params), _trade: PhantomData }
+    }
+
+    /// Next page of trades not returned yet, `None` once the range is exhausted.
+    /// A page can be empty when a crowded millisecond has to be requested on its own.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<T>>> {
+        let (endpoint, query) = match self.pager.next_request() {
+            Some(request) => request,
+            None => return Ok(None),
+        };
+
+        let page = match self.client.get(endpoint, query).await {
+            Ok(data) => decode::<Vec<T>>(data.as_str()).and_then(|page| self.pager.advance(page)),
+            Err(e) => Err(e),
+        };
+
+        match page {
+            Ok(page) => Ok(Some(page)),
+            Err(e) => {
+                self.pager.finish();
+                Err(e)
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::auth::NonceProvider;
+use crate::client::ClientConfig;
+use crate::errors::*;
+use crate::events::WsEvent;
+use crate::candles::CandleKey;
+use crate::derivatives::StatusKey;
+use crate::symbol::Symbol;
+use crate::websockets::{
+    auth_message, books_message, candles_message, parse_event, raw_books_message, status_message,
+    subscribe_message, unsubscribe_message,
+};
+use futures_util::stream::{SplitStream, Stream, StreamExt};
+use futures_util::{ready, SinkExt};
+use log::*;
+use serde_json::Value;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tungstenite::Message;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Cloneable handle to an async websocket connection.
+///
+/// Commands are queued to a writer task and sent immediately, independently of
+/// how fast the `EventStream` is being polled.
+#[derive(Clone)]
+pub struct WebSockets {
+    tx: mpsc::UnboundedSender<Message>,
+    nonce_provider: Arc<dyn NonceProvider>,
+}
+
+/// Stream of decoded events. Heartbeats are filtered out and the stream ends
+/// when the server closes the connection.
+pub struct EventStream {
+    inner: SplitStream<Socket>,
+}
+
+impl WebSockets {
+    /// Connects to the default endpoint. Must be called from within a tokio runtime.
+    pub async fn connect() -> Result<(WebSockets, EventStream)> {
+        WebSockets::connect_with_config(&ClientConfig::default()).await
+    }
+
+    pub async fn connect_with_config(config: &ClientConfig) -> Result<(WebSockets, EventStream)> {
+        let (socket, _) = match connect_async(config.websocket_url.as_str()).await {
+            Ok(answer) => answer,
+            Err(e) => return Err(Error::Other(format!("Error during handshake {}", e))),
+        };
+        let (mut sink, stream) = socket.split();
+        let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+
+        tokio::spawn(async move {
+            while let Some(msg) = rx.recv().await {
+                let close = matches!(msg, Message::Close(_));
+                if let Err(e) = sink.send(msg).await {
+                    error!("Websocket write failed: {}", e);
+                    break;
+                }
+                if close {
+                    break;
+                }
+            }
+            let _ = sink.close().await;
+        });
+
+        let handle = WebSockets {
+            tx,
+            nonce_provider: config.nonce_provider.clone(),
+        };
+
+        Ok((handle, EventStream { inner: stream }))
+    }
+
+    /// Authenticates the connection, see `websockets::WebSockets::auth`.
+    pub fn auth<S>(&self, api_key: S, api_secret: S, dms: bool, filters: &[&str]) -> Result<()>
+        where S: AsRef<str>
+    {
+        let msg = auth_message(self.nonce_provider.as_ref(), api_key.as_ref(), api_secret.as_ref(), dms, filters)?;
+        self.send_json(msg)
+    }
+
+    pub fn subscribe_ticker<S>(&self, symbol: S) -> Result<()>
+        where S: TryInto<Symbol>, Error: From<S::Error>
+    {
+        self.send_json(subscribe_message("ticker", &symbol.try_into()?))
+    }
+
+    pub fn subscribe_trades<S>(&self, symbol: S) -> Result<()>
+        where S: TryInto<Symbol>, Error: From<S::Error>
+    {
+        self.send_json(subscribe_message("trades", &symbol.try_into()?))
+    }
+
+    pub fn subscribe_candles(&self, key: &CandleKey) -> Result<()> {
+        self.send_json(candles_message(key))
+    }
+
+    pub fn subscribe_status(&self, key: &StatusKey) -> Result<()> {
+        self.send_json(status_message(key))
+    }
+
+    pub fn subscribe_books<S, P, F>(&self, symbol: S, prec: P, freq: F, len: u32) -> Result<()>
+        where S: TryInto<Symbol>, Error: From<S::Error>, P: Into<String>, F: Into<String>
+    {
+        self.send_json(books_message(&symbol.try_into()?, prec.into(), freq.into(), len))
+    }
+
+    pub fn subscribe_raw_books<S>(&self, symbol: S) -> Result<()>
+        where S: TryInto<Symbol>, Error: From<S::Error>
+    {
+        self.send_json(raw_books_message(&symbol.try_into()?))
+    }
+
+    pub fn unsubscribe(&self, chan_id: u32) -> Result<()> {
+        self.send_json(unsubscribe_message(chan_id))
+    }
+
+    /// Sends a raw text frame.
+    pub fn send(&self, raw: &str) -> Result<()> {
+        self.tx.send(Message::Text(raw.to_string()))
+            .map_err(|e| Error::Other(format!("Not able to send a message: {}", e)))
+    }
+
+    /// Closes the connection. The `EventStream` ends once the server acknowledges.
+    pub fn shutdown(&self) -> Result<()> {
+        self.tx.send(Message::Close(None))
+            .map_err(|e| Error::Other(format!("Error during shutdown: {}", e)))
+    }
+
+    fn send_json(&self, msg: Value) -> Result<()> {
+        self.send(&msg.to_string())
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<WsEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let message = match ready!(self.inner.poll_next_unpin(cx)) {
+                Some(Ok(message)) => message,
+                Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                None => return Poll::Ready(None),
+            };
+
+            match message {
+                Message::Text(text) => match parse_event(&text) {
+                    Ok(Some(event)) => return Poll::Ready(Some(Ok(event))),
+                    Ok(None) => continue,
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                },
+                Message::Close(_) => return Poll::Ready(None),
+                Message::Binary(_) |
+                Message::Ping(_) |
+                Message::Pong(_) |
+                Message::Frame(_) => continue,
+            }
+        }
+    }
+}
@@ -1,6 +1,11 @@
-use errors::*;
+use crate::errors::*;
 use hex::encode;
 use ring::hmac;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn sign_payload(secret: &[u8], payload: &[u8]) -> Result<String> {
@@ -10,11 +15,170 @@ pub fn sign_payload(secret: &[u8], payload: &[u8]) -> Result<String> {
     Ok(signature)
 }
 
+/// Generates a nonce from the process wide default provider.
 pub fn generate_nonce() -> Result<String> {
-    let start = SystemTime::now();
-    let since_epoch = start.duration_since(UNIX_EPOCH)?;
+    default_nonce_provider().next_nonce().map(|n| n.to_string())
+}
+
+/// Source of the `bfx-nonce` / `authNonce` values.
+///
+/// Bitfinex rejects any nonce that is not greater than the last one seen for
+/// an API key, so every client signing with the same key must draw from the
+/// same provider.
+pub trait NonceProvider: Send + Sync {
+    fn next_nonce(&self) -> Result<u64>;
+}
+
+/// Strictly increasing, microsecond based nonces, safe to share between threads.
+#[derive(Debug, Default)]
+pub struct AtomicNonce {
+    last: AtomicU64,
+}
+
+impl AtomicNonce {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Never hands out a nonce lower than or equal to `last`.
+    pub fn starting_after(last: u64) -> Self {
+        AtomicNonce { last: AtomicU64::new(last) }
+    }
+}
+
+impl NonceProvider for AtomicNonce {
+    fn next_nonce(&self) -> Result<u64> {
+        let now = now_micros()?;
+        let mut last = self.last.load(Ordering::Relaxed);
+        loop {
+            let next = now.max(last + 1);
+            match self.last.compare_exchange_weak(last, next, Ordering::AcqRel, Ordering::Relaxed) {
+                Ok(_) => return Ok(next),
+                Err(current) => last = current,
+            }
+        }
+    }
+}
+
+/// `AtomicNonce` that writes every nonce to a file, so a restarted process
+/// resumes above the last value it used.
+#[derive(Debug)]
+pub struct PersistentNonce {
+    path: PathBuf,
+    inner: AtomicNonce,
+    lock: Mutex<()>,
+}
+
+impl PersistentNonce {
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<Self> {
+        let path = path.into();
+        let last = match fs::read_to_string(&path) {
+            // Left empty by a crash before writes went through a temporary file
+            Ok(content) if content.trim().is_empty() => now_micros()?,
+            Ok(content) => content.trim().parse::<u64>()
+                .map_err(|e| Error::Other(format!("Invalid nonce file {}: {}", path.display(), e)))?,
+            Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(PersistentNonce {
+            path,
+            inner: AtomicNonce::starting_after(last),
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Replaces the nonce file through a synced temporary file, so a crash or a
+    /// full disk never leaves it empty or half written.
+    fn persist(&self, nonce: u64) -> Result<()> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+
+        let mut file = File::create(&tmp)?;
+        file.write_all(nonce.to_string().as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, &self.path)?;
+
+        Ok(())
+    }
+}
+
+impl NonceProvider for PersistentNonce {
+    fn next_nonce(&self) -> Result<u64> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let nonce = self.inner.next_nonce()?;
+        self.persist(nonce)?;
+
+        Ok(nonce)
+    }
+}
+
+/// Provider used by every `Client` and `WebSockets` unless one is set in `ClientConfig`.
+pub fn default_nonce_provider() -> Arc<dyn NonceProvider> {
+    static DEFAULT: OnceLock<Arc<AtomicNonce>> = OnceLock::new();
+
+    DEFAULT.get_or_init(|| Arc::new(AtomicNonce::new())).clone()
+}
+
+fn now_micros() -> Result<u64> {
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH)?;
+
+    Ok(since_epoch.as_secs() * 1_000_000 + u64::from(since_epoch.subsec_micros()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::thread;
+
+    #[test]
+    fn test_atomic_nonce_is_unique_across_threads() {
+        let provider = Arc::new(AtomicNonce::new());
+
+        let handles: Vec<_> = (0..4).map(|_| {
+            let provider = provider.clone();
+            thread::spawn(move || {
+                let nonces: Vec<u64> = (0..1000).map(|_| provider.next_nonce().unwrap()).collect();
+                assert!(nonces.windows(2).all(|w| w[0] < w[1]));
+                nonces
+            })
+        }).collect();
+
+        let mut seen = HashSet::new();
+        for handle in handles {
+            for nonce in handle.join().unwrap() {
+                assert!(seen.insert(nonce));
+            }
+        }
+    }
+
+    #[test]
+    fn test_persistent_nonce_resumes() {
+        let path = std::env::temp_dir().join(format!("bitfinex-nonce-{}", std::process::id()));
+        fs::write(&path, (u64::MAX / 2).to_string()).unwrap();
+
+        let provider = PersistentNonce::open(&path).unwrap();
+        assert_eq!(provider.next_nonce().unwrap(), u64::MAX / 2 + 1);
+        drop(provider);
+
+        let provider = PersistentNonce::open(&path).unwrap();
+        assert_eq!(provider.next_nonce().unwrap(), u64::MAX / 2 + 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_persistent_nonce_empty_file() {
+        let path = std::env::temp_dir().join(format!("bitfinex-nonce-empty-{}", std::process::id()));
+        fs::write(&path, "").unwrap();
 
-    let timestamp = since_epoch.as_secs() * 1000 + since_epoch.subsec_nanos() as u64 / 1_000_000;
+        let before = now_micros().unwrap();
+        let provider = PersistentNonce::open(&path).unwrap();
+        let nonce = provider.next_nonce().unwrap();
+        assert!(nonce > before);
+        assert_eq!(fs::read_to_string(&path).unwrap(), nonce.to_string());
 
-    Ok((timestamp + 1).to_string())
+        fs::remove_file(&path).unwrap();
+    }
 }
@@ -0,0 +1,216 @@
+use crate::errors::*;
+use crate::number::Number;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Words taken by the signature and recovery id at the end of the data part
+static SIGNATURE_WORDS: usize = 104;
+static CHECKSUM_WORDS: usize = 6;
+static TIMESTAMP_WORDS: usize = 7;
+
+/// Expiry of an invoice without an `x` field (seconds)
+pub static DEFAULT_EXPIRY: u64 = 3600;
+
+static TAG_PAYMENT_HASH: u8 = 1;
+static TAG_EXPIRY: u8 = 6;
+static TAG_DESCRIPTION: u8 = 13;
+
+/// The parts of a BOLT11 Lightning invoice needed to track a payment, decoded
+/// locally. The signature is not checked.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Bolt11 {
+    /// Currency prefix, `bc` on mainnet
+    pub network: String,
+    /// Requested amount in millisatoshis, `None` when the payer chooses it
+    pub amount_msat: Option<u64>,
+    /// Creation time (seconds)
+    pub timestamp: u64,
+    /// Seconds after `timestamp` the invoice stops being payable
+    pub expiry: u64,
+    /// Hex encoded payment hash
+    pub payment_hash: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Bolt11 {
+    /// Requested amount in BTC.
+    pub fn amount(&self) -> Option<Number> {
+        self.amount_msat.and_then(|msat| {
+            format!("{}.{:011}", msat / 100_000_000_000, msat % 100_000_000_000).parse().ok()
+        })
+    }
+
+    /// Time the invoice expires (seconds).
+    pub fn expires_at(&self) -> u64 {
+        self.timestamp.saturating_add(self.expiry)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs() >= self.expires_at())
+            .unwrap_or(false)
+    }
+}
+
+impl FromStr for Bolt11 {
+    type Err = Error;
+
+    fn from_str(invoice: &str) -> Result<Self> {
+        let invoice = invoice.trim().to_lowercase();
+        let invoice = invoice.strip_prefix("lightning:").unwrap_or(&invoice);
+
+        let (hrp, data) = invoice.rsplit_once('1').ok_or_else(|| invalid("missing separator"))?;
+        let network_amount = hrp.strip_prefix("ln").ok_or_else(|| invalid("missing ln prefix"))?;
+
+        let words = data
+            .chars()
+            .map(|c| CHARSET.find(c).map(|w| w as u8))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| invalid("invalid character"))?;
+
+        if words.len() < TIMESTAMP_WORDS + SIGNATURE_WORDS + CHECKSUM_WORDS {
+            return Err(invalid("too short"));
+        }
+        if !checksum_valid(hrp, &words) {
+            return Err(invalid("invalid checksum"));
+        }
+
+        let (network, amount_msat) = match network_amount.find(|c: char| c.is_ascii_digit()) {
+            Some(i) => (&network_amount[..i], Some(parse_amount(&network_amount[i..])?)),
+            None => (network_amount, None),
+        };
+
+        let mut bolt11 = Bolt11 {
+            network: network.to_owned(),
+            amount_msat,
+            timestamp: to_u64(&words[..TIMESTAMP_WORDS]),
+            expiry: DEFAULT_EXPIRY,
+            payment_hash: None,
+            description: None,
+        };
+
+        let mut fields = &words[TIMESTAMP_WORDS..words.len() - SIGNATURE_WORDS - CHECKSUM_WORDS];
+        while fields.len() >= 3 {
+            let len = (fields[1] as usize) << 5 | fields[2] as usize;
+            let value = fields.get(3..3 + len).ok_or_else(|| invalid("truncated field"))?;
+
+            match fields[0] {
+                tag if tag == TAG_PAYMENT_HASH => bolt11.payment_hash = Some(hex::encode(to_bytes(value))),
+                tag if tag == TAG_EXPIRY => bolt11.expiry = to_u64(value),
+                tag if tag == TAG_DESCRIPTION => {
+                    bolt11.description = Some(String::from_utf8_lossy(&to_bytes(value)).into_owned())
+                }
+                _ => {}
+            }
+
+            fields = &fields[3 + len..];
+        }
+
+        Ok(bolt11)
+    }
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidRequest(format!("invalid BOLT11 invoice: {}", reason))
+}
+
+/// `2500u` into millisatoshis, the multiplier being a fraction of a bitcoin.
+fn parse_amount(amount: &str) -> Result<u64> {
+    let (digits, multiplier) = match amount.char_indices().last() {
+        Some((i, c)) if !c.is_ascii_digit() => (&amount[..i], Some(c)),
+        _ => (amount, None),
+    };
+    let value: u64 = digits.parse().map_err(|_| invalid("invalid amount"))?;
+
+    let msat = match multiplier {
+        None => value.checked_mul(100_000_000_000),
+        Some('m') => value.checked_mul(100_000_000),
+        Some('u') => value.checked_mul(100_000),
+        Some('n') => value.checked_mul(100),
+        Some('p') if value.is_multiple_of(10) => Some(value / 10),
+        _ => None,
+    };
+
+    msat.ok_or_else(|| invalid("invalid amount"))
+}
+
+fn checksum_valid(hrp: &str, words: &[u8]) -> bool {
+    let expanded = hrp.bytes().map(|c| c >> 5)
+        .chain(Some(0))
+        .chain(hrp.bytes().map(|c| c & 31))
+        .chain(words.iter().copied());
+
+    polymod(expanded) == 1
+}
+
+fn polymod<I: Iterator<Item = u8>>(values: I) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    values.fold(1, |chk, value| {
+        let top = chk >> 25;
+        let chk = (chk & 0x1ffffff) << 5 ^ value as u32;
+        GENERATOR.iter().enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(chk, |chk, (_, gen)| chk ^ gen)
+    })
+}
+
+fn to_u64(words: &[u8]) -> u64 {
+    words.iter().fold(0, |value, w| value << 5 | *w as u64)
+}
+
+/// Regroups 5-bit words into bytes, dropping the padding bits.
+fn to_bytes(words: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(words.len() * 5 / 8);
+    let (mut acc, mut bits) = (0u32, 0);
+
+    for w in words {
+        acc = acc << 5 | *w as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static COFFEE: &str = "lnbc2500u1pvjluezpp5qqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqqqsyqcyq5rqwzqfqypqdq5xysxxatsyp3k7enxv4jsxqzpuaztrnwngzn3kdzw5hydlzf03qdgm2hdq27cqv3agm2awhz5se903vruatfhq77w3ls4evs3ch9zw97j25emudupq63nyw24cg27h2rspfj9srp";
+
+    #[test]
+    fn test_decode() {
+        let bolt11: Bolt11 = COFFEE.parse().unwrap();
+        assert_eq!(bolt11.network, "bc");
+        assert_eq!(bolt11.amount_msat, Some(250_000_000));
+        assert_eq!(bolt11.amount(), Some("0.0025".parse::<Number>().unwrap()));
+        assert_eq!(bolt11.timestamp, 1496314658);
+        assert_eq!(bolt11.expires_at(), 1496314718);
+        assert_eq!(bolt11.description.as_deref(), Some("1 cup coffee"));
+        assert_eq!(
+            bolt11.payment_hash.as_deref(),
+            Some("0001020304050607080900010203040506070809000102030405060708090102")
+        );
+        assert!(bolt11.is_expired());
+
+        let upper: Bolt11 = format!("lightning:{}", COFFEE.to_uppercase()).parse().unwrap();
+        assert_eq!(upper, bolt11);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(COFFEE.replace("2500u", "2500m").parse::<Bolt11>().is_err());
+        assert!("lnbc1qqqqqq".parse::<Bolt11>().is_err());
+        assert_eq!(parse_amount("25p").ok(), None);
+        assert_eq!(parse_amount("10p").ok(), Some(1));
+        assert_eq!(parse_amount("1").ok(), Some(100_000_000_000));
+    }
+}
@@ -1,20 +1,22 @@
-use client::*;
-use errors::*;
-use serde_json::from_str;
+use crate::client::*;
+use crate::errors::*;
+use crate::number::Number;
+use crate::precision::Precision;
+use crate::symbol::{self, Symbol};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TradingPair {
-    pub price: f64,
+    pub price: Number,
     pub count: i64,
-    pub amount: f64,
+    pub amount: Number,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FundingCurrency {
-    pub rate: f64,
+    pub rate: Number,
     pub period: f64,
     pub count: i64,
-    pub amount: f64,
+    pub amount: Number,
 }
 
 #[derive(Clone)]
@@ -26,34 +28,152 @@ pub struct Book {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RawBook {
     pub order_id: i64,
-    pub price: f64,
-    pub amount: f64,
+    pub price: Number,
+    pub amount: Number,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RawFundingBook {
+    pub offer_id: i64,
+    pub period: i64,
+    pub rate: Number,
+    pub amount: Number,
+}
+
+/// Number of price levels (or orders, for raw books) returned per side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookLength {
+    Len1,
+    Len25,
+    Len100,
+}
+
+impl BookLength {
+    pub fn as_u32(&self) -> u32 {
+        match *self {
+            BookLength::Len1 => 1,
+            BookLength::Len25 => 25,
+            BookLength::Len100 => 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BookParams {
+    /// Price levels per side, Bitfinex defaults to 25
+    pub len: Option<BookLength>,
+}
+
+impl BookParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(mut self, len: BookLength) -> Self {
+        self.len = Some(len);
+        self
+    }
+
+    /// Leaves `len` out when unset rather than sending it empty.
+    pub fn to_query(&self) -> String {
+        self.len
+            .map(|len| format!("len={}", len.as_u32()))
+            .unwrap_or_default()
+    }
+}
+
+/// Endpoint of an aggregated book; raw books have their own methods and response types.
+pub(crate) fn book_endpoint(symbol: Symbol, precision: Precision) -> Result<String> {
+    if precision == Precision::R0 {
+        return Err(Error::InvalidRequest("R0 books are fetched with the raw_* methods".into()));
+    }
+
+    Ok(format!("book/{}/{}", symbol, precision))
+}
+
+impl Default for Book {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Book {
     pub fn new() -> Self {
-        Book { client: Client::new(None, None) }
+        Book::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Book {
+            client: Client::with_config(None, None, config),
+        }
+    }
+
+    pub fn funding_currency<C>(&self, currency: C, precision: Precision, params: &BookParams) -> Result<Vec<FundingCurrency>>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
+    {
+        let endpoint = book_endpoint(Symbol::Funding(currency.try_into()?), precision)?;
+        let data = self.client.get(endpoint, params.to_query())?;
+
+        let book: Vec<FundingCurrency> = decode(data.as_str())?;
+
+        Ok(book)
     }
 
-    pub fn funding_currency<S>(&self, symbol: S, precision: S) -> Result<Vec<FundingCurrency>>
-        where S: Into<String>
+    pub fn trading_pair<P>(&self, pair: P, precision: Precision, params: &BookParams) -> Result<Vec<TradingPair>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
     {
-        let endpoint: String = format!("book/f{}/{}", symbol.into(), precision.into());
-        let data = self.client.get(endpoint, String::new())?;
+        let endpoint = book_endpoint(Symbol::Trading(pair.try_into()?), precision)?;
+        let data = self.client.get(endpoint, params.to_query())?;
 
-        let book: Vec<FundingCurrency> = from_str(data.as_str())?;
+        let book: Vec<TradingPair> = decode(data.as_str())?;
 
         Ok(book)
     }
 
-    pub fn trading_pair<S>(&self, symbol: S, precision: S) -> Result<Vec<TradingPair>>
-        where S: Into<String>
-    {    
-        let endpoint: String = format!("book/t{}/{}", symbol.into(), precision.into());
-        let data = self.client.get(endpoint, String::new())?;
+    /// Individual funding offers, with their ids.
+    pub fn raw_funding_currency<C>(&self, currency: C, params: &BookParams) -> Result<Vec<RawFundingBook>>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
+    {
+        let endpoint: String = format!("book/{}/{}", currency.try_into()?, Precision::R0);
+        let data = self.client.get(endpoint, params.to_query())?;
 
-        let book: Vec<TradingPair> = from_str(data.as_str())?;
+        let book: Vec<RawFundingBook> = decode(data.as_str())?;
 
         Ok(book)
     }
-}
\ No newline at end of file
+
+    /// Individual orders, with their ids.
+    pub fn raw_trading_pair<P>(&self, pair: P, params: &BookParams) -> Result<Vec<RawBook>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
+    {
+        let endpoint: String = format!("book/{}/{}", pair.try_into()?, Precision::R0);
+        let data = self.client.get(endpoint, params.to_query())?;
+
+        let book: Vec<RawBook> = decode(data.as_str())?;
+
+        Ok(book)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::from_str;
+    use crate::pairs::BTCUSD;
+
+    #[test]
+    fn test_book_request() {
+        assert_eq!(book_endpoint(Symbol::Trading(BTCUSD), Precision::P4).unwrap(), "book/tBTCUSD/P4");
+        assert!(book_endpoint(Symbol::Trading(BTCUSD), Precision::R0).is_err());
+        assert_eq!(BookParams::new().len(BookLength::Len100).to_query(), "len=100");
+        assert_eq!(BookParams::new().to_query(), "");
+    }
+
+    #[test]
+    fn test_raw_funding_book() {
+        let data = "[[658116978,30,0.00035,-1000.5],[658116969,2,0.00032,250]]";
+        let book: Vec<RawFundingBook> = from_str(data).expect("parsed");
+        assert_eq!(book[0].offer_id, 658116978);
+        assert_eq!(book[1].period, 2);
+    }
+}
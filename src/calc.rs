@@ -0,0 +1,171 @@
+use crate::client::*;
+use crate::errors::*;
+use crate::number::Number;
+use crate::symbol::{self, FundingCurrency, TradingPair};
+use std::fmt;
+
+/// Average execution price of `amount` against the current order book.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TradeAvg {
+    pub price_avg: Number,
+    pub amount: Number,
+}
+
+/// Average rate of taking `amount` of funding from the current book.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FundingAvg {
+    pub rate_avg: f64,
+    pub amount: Number,
+}
+
+#[derive(Debug, Clone)]
+pub struct TradeAvgParams {
+    /// Amount to buy, negative to sell
+    amount: String,
+    /// Stop at this price when walking the book
+    price_limit: Option<String>,
+}
+
+impl TradeAvgParams {
+    pub fn new<N: fmt::Display>(amount: N) -> Self {
+        TradeAvgParams { amount: amount.to_string(), price_limit: None }
+    }
+
+    pub fn price_limit<N: fmt::Display>(mut self, price_limit: N) -> Self {
+        self.price_limit = Some(price_limit.to_string());
+        self
+    }
+
+    pub fn to_query(&self, pair: &TradingPair) -> String {
+        let mut query = format!("symbol={}&amount={}", pair, self.amount);
+        if let Some(ref price_limit) = self.price_limit {
+            query.push_str(&format!("&price_limit={}", price_limit));
+        }
+        query
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FundingAvgParams {
+    /// Amount to borrow, negative to lend
+    amount: String,
+    /// Funding period in days
+    period: u32,
+    /// Stop at this rate when walking the book
+    rate_limit: Option<String>,
+}
+
+impl FundingAvgParams {
+    pub fn new<N: fmt::Display>(amount: N, period: u32) -> Self {
+        FundingAvgParams { amount: amount.to_string(), period, rate_limit: None }
+    }
+
+    pub fn rate_limit<N: fmt::Display>(mut self, rate_limit: N) -> Self {
+        self.rate_limit = Some(rate_limit.to_string());
+        self
+    }
+
+    pub fn to_query(&self, currency: &FundingCurrency) -> String {
+        let mut query = format!("symbol={}&amount={}&period={}", currency, self.amount, self.period);
+        if let Some(ref rate_limit) = self.rate_limit {
+            query.push_str(&format!("&rate_limit={}", rate_limit));
+        }
+        query
+    }
+}
+
+/// Public calculation endpoints, useful to size orders before submitting them.
+#[derive(Clone)]
+pub struct Calc {
+    client: Client,
+}
+
+impl Default for Calc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Calc {
+    pub fn new() -> Self {
+        Calc::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Calc {
+            client: Client::with_config(None, None, config),
+        }
+    }
+
+    pub fn trade_avg<P>(&self, pair: P, params: &TradeAvgParams) -> Result<TradeAvg>
+        where P: TryInto<TradingPair>, Error: From<P::Error>
+    {
+        let query = params.to_query(&pair.try_into()?);
+        let data = self.client.post("calc/trade/avg".into(), query, String::new())?;
+
+        let avg: TradeAvg = decode(data.as_str())?;
+
+        Ok(avg)
+    }
+
+    pub fn funding_avg<C>(&self, currency: C, params: &FundingAvgParams) -> Result<FundingAvg>
+        where C: TryInto<FundingCurrency>, Error: From<C::Error>
+    {
+        let query = params.to_query(&currency.try_into()?);
+        let data = self.client.post("calc/trade/avg".into(), query, String::new())?;
+
+        let avg: FundingAvg = decode(data.as_str())?;
+
+        Ok(avg)
+    }
+
+    /// Exchange rate between two currency codes, e.g. "BTC" and "USD".
+    pub fn fx(&self, from: &str, to: &str) -> Result<Number> {
+        let payload = fx_payload(from, to)?;
+        let data = self.client.post("calc/fx".into(), String::new(), payload)?;
+
+        fx_rate(decode(data.as_str())?)
+    }
+}
+
+/// Currency codes are sent without prefix, a `fUSD` or `tBTCUSD` symbol is refused.
+pub(crate) fn fx_payload(from: &str, to: &str) -> Result<String> {
+    for currency in [from, to] {
+        if !symbol::is_ticker(currency) {
+            return Err(Error::InvalidSymbol(currency.to_owned()));
+        }
+    }
+
+    Ok(json!({"ccy1": from, "ccy2": to}).to_string())
+}
+
+pub(crate) fn fx_rate(rate: Vec<Number>) -> Result<Number> {
+    rate.into_iter().next().ok_or_else(|| Error::Other("Empty response for calc/fx".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::from_str;
+    use crate::pairs::BTCUSD;
+
+    #[test]
+    fn test_trade_avg() {
+        let params = TradeAvgParams::new(-1.5).price_limit(9000);
+        assert_eq!(params.to_query(&BTCUSD), "symbol=tBTCUSD&amount=-1.5&price_limit=9000");
+
+        let params = FundingAvgParams::new(1000, 30);
+        assert_eq!(params.to_query(&FundingCurrency::new("USD")), "symbol=fUSD&amount=1000&period=30");
+
+        let avg: FundingAvg = from_str("[0.0002,1000]").expect("parsed");
+        assert_eq!(avg.rate_avg, 0.0002);
+    }
+
+    #[test]
+    fn test_fx() {
+        assert_eq!(fx_payload("BTC", "USD").unwrap(), "{\"ccy1\":\"BTC\",\"ccy2\":\"USD\"}");
+        assert!(fx_payload("BTC", "fUSD").is_err());
+        assert!(fx_payload("tBTCUSD", "USD").is_err());
+        assert_eq!(fx_rate(from_str("[9218.5]").expect("parsed")).unwrap(), "9218.5".parse::<Number>().unwrap());
+    }
+}
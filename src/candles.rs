@@ -1,6 +1,9 @@
-use client::*;
-use errors::*;
-use serde_json::from_str;
+use crate::client::*;
+use crate::errors::*;
+use crate::number::Number;
+use crate::symbol::{FundingCurrency, Symbol, TradingPair};
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Default)]
 pub struct CandleHistoryParams {
@@ -31,16 +34,16 @@ impl CandleHistoryParams {
         format!("{}={}&{}={}&{}={}&{}={}",
             "limit", self.limit
                          .map(|a| a.to_string())
-                         .unwrap_or("".into()),
+                         .unwrap_or_default(),
             "start", self.start
                          .map(|a| a.to_string())
-                         .unwrap_or("".into()),
+                         .unwrap_or_default(),
             "end", self.end
                        .map(|a| a.to_string())
-                       .unwrap_or("".into()),
+                       .unwrap_or_default(),
             "sort", self.sort
                         .map(|a| if a { "1" } else { "0" })
-                        .unwrap_or("".into()),
+                        .unwrap_or(""),
         )
     }
 }
@@ -48,11 +51,175 @@ impl CandleHistoryParams {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Candle { 
     pub timestamp: i64,   
-    pub open: f64,                   
-    pub close: f64,
-    pub high: f64,
-    pub low: f64,
-    pub volume: f64                        
+    pub open: Number,                   
+    pub close: Number,
+    pub high: Number,
+    pub low: Number,
+    pub volume: Number                        
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Timeframe {
+    OneMinute,
+    FiveMinutes,
+    FifteenMinutes,
+    ThirtyMinutes,
+    OneHour,
+    ThreeHours,
+    SixHours,
+    TwelveHours,
+    OneDay,
+    OneWeek,
+    FourteenDays,
+    OneMonth,
+}
+
+impl Timeframe {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Timeframe::OneMinute => "1m",
+            Timeframe::FiveMinutes => "5m",
+            Timeframe::FifteenMinutes => "15m",
+            Timeframe::ThirtyMinutes => "30m",
+            Timeframe::OneHour => "1h",
+            Timeframe::ThreeHours => "3h",
+            Timeframe::SixHours => "6h",
+            Timeframe::TwelveHours => "12h",
+            Timeframe::OneDay => "1D",
+            Timeframe::OneWeek => "1W",
+            Timeframe::FourteenDays => "14D",
+            Timeframe::OneMonth => "1M",
+        }
+    }
+}
+
+impl fmt::Display for Timeframe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Timeframe {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let timeframe = match value {
+            "1m" => Timeframe::OneMinute,
+            "5m" => Timeframe::FiveMinutes,
+            "15m" => Timeframe::FifteenMinutes,
+            "30m" => Timeframe::ThirtyMinutes,
+            "1h" => Timeframe::OneHour,
+            "3h" => Timeframe::ThreeHours,
+            "6h" => Timeframe::SixHours,
+            "12h" => Timeframe::TwelveHours,
+            "1D" => Timeframe::OneDay,
+            "1W" | "7D" => Timeframe::OneWeek,
+            "14D" => Timeframe::FourteenDays,
+            "1M" => Timeframe::OneMonth,
+            _ => return Err(Error::InvalidRequest(format!("unknown candle timeframe '{}'", value))),
+        };
+
+        Ok(timeframe)
+    }
+}
+
+/// Offer period of a funding candle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FundingPeriod {
+    /// Offers of exactly this many days, `p30`
+    Days(u32),
+
+    /// Offers of `period_min` to `period_max` days aggregated over
+    /// `aggregation` (10 or 30) days, `a30:p2:p30`
+    Aggregate { aggregation: u32, period_min: u32, period_max: u32 },
+}
+
+impl fmt::Display for FundingPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FundingPeriod::Days(days) => write!(f, "p{}", days),
+            FundingPeriod::Aggregate { aggregation, period_min, period_max } => {
+                write!(f, "a{}:p{}:p{}", aggregation, period_min, period_max)
+            }
+        }
+    }
+}
+
+/// Key of a candle series: `trade:1m:tBTCUSD`, `trade:1m:fUSD:p30` or
+/// `trade:1m:fUSD:a30:p2:p30`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CandleKey {
+    timeframe: Timeframe,
+    symbol: Symbol,
+    period: Option<FundingPeriod>,
+}
+
+impl CandleKey {
+    pub fn trading(timeframe: Timeframe, pair: TradingPair) -> Self {
+        CandleKey { timeframe, symbol: Symbol::Trading(pair), period: None }
+    }
+
+    /// Candles of the funding offers of `currency`, funding keys always carry a period.
+    pub fn funding(timeframe: Timeframe, currency: FundingCurrency, period: FundingPeriod) -> Self {
+        CandleKey { timeframe, symbol: Symbol::Funding(currency), period: Some(period) }
+    }
+
+    pub fn timeframe(&self) -> Timeframe {
+        self.timeframe
+    }
+
+    pub fn symbol(&self) -> &Symbol {
+        &self.symbol
+    }
+
+    pub fn period(&self) -> Option<FundingPeriod> {
+        self.period
+    }
+}
+
+impl fmt::Display for CandleKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "trade:{}:{}", self.timeframe, self.symbol)?;
+        if let Some(period) = self.period {
+            write!(f, ":{}", period)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the keys echoed back in candle `subscribed` events.
+impl FromStr for CandleKey {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let invalid = || Error::InvalidRequest(format!("invalid candle key '{}'", value));
+        let mut parts = value.split(':');
+
+        if parts.next() != Some("trade") {
+            return Err(invalid());
+        }
+        let timeframe: Timeframe = parts.next().ok_or_else(invalid)?.parse()?;
+
+        // Long trading pairs contain a colon themselves, e.g. tTESTBTC:TESTUSD
+        let rest: Vec<&str> = parts.collect();
+        if rest.first().is_some_and(|symbol| symbol.starts_with('t')) {
+            return Ok(CandleKey::trading(timeframe, rest.join(":").parse()?));
+        }
+
+        let days = |part: &str| part.strip_prefix('p').and_then(|days| days.parse().ok()).ok_or_else(invalid);
+        let period = match rest[..] {
+            [_, period] => FundingPeriod::Days(days(period)?),
+            [_, aggregation, period_min, period_max] => FundingPeriod::Aggregate {
+                aggregation: aggregation.strip_prefix('a').and_then(|a| a.parse().ok()).ok_or_else(invalid)?,
+                period_min: days(period_min)?,
+                period_max: days(period_max)?,
+            },
+            _ => return Err(invalid()),
+        };
+
+        Ok(CandleKey::funding(timeframe, rest[0].parse()?, period))
+    }
 }
 
 #[derive(Clone)]
@@ -60,37 +227,61 @@ pub struct Candles {
     client: Client,
 }
 
+impl Default for Candles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Candles {
     pub fn new() -> Self {
+        Candles::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
         Candles {
-            client: Client::new(None, None),
+            client: Client::with_config(None, None, config),
         }
     }
 
-    pub fn last<S>(&self, symbol: S, timeframe: S) -> Result<Candle>
-        where S: Into<String>
-    {    
-        let endpoint: String = format!("candles/trade:{}:t{}/last", timeframe.into(), symbol.into());
+    pub fn last(&self, key: &CandleKey) -> Result<Candle> {
+        let endpoint: String = format!("candles/{}/last", key);
         let data = self.client.get(endpoint, String::new())?;
 
-        let history: Candle = from_str(data.as_str())?;
+        let history: Candle = decode(data.as_str())?;
 
         Ok(history)
     }    
 
-    pub fn history<S>(
-        &self,
-        symbol: S,
-        timeframe: S,
-        params: &CandleHistoryParams,
-    ) -> Result<Vec<Candle>>
-        where S: Into<String>
-    {    
-        let endpoint: String = format!("candles/trade:{}:t{}/hist", timeframe.into(), symbol.into());
+    pub fn history(&self, key: &CandleKey, params: &CandleHistoryParams) -> Result<Vec<Candle>> {
+        let endpoint: String = format!("candles/{}/hist", key);
         let data = self.client.get(endpoint, params.to_query())?;
 
-        let history: Vec<Candle> = from_str(data.as_str())?;
+        let history: Vec<Candle> = decode(data.as_str())?;
 
         Ok(history)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pairs::{BTCUSD, TESTBTCTESTUSD};
+
+    #[test]
+    fn test_candle_key() {
+        let usd = FundingCurrency::new("USD");
+
+        assert_eq!(CandleKey::trading(Timeframe::OneMinute, BTCUSD).to_string(), "trade:1m:tBTCUSD");
+        assert_eq!(CandleKey::funding(Timeframe::OneDay, usd.clone(), FundingPeriod::Days(30)).to_string(), "trade:1D:fUSD:p30");
+
+        let aggregate = FundingPeriod::Aggregate { aggregation: 30, period_min: 2, period_max: 30 };
+        let key = CandleKey::funding(Timeframe::OneHour, usd, aggregate);
+        assert_eq!(key.to_string(), "trade:1h:fUSD:a30:p2:p30");
+
+        for key in [key, CandleKey::trading(Timeframe::OneMonth, TESTBTCTESTUSD)] {
+            assert_eq!(key.to_string().parse::<CandleKey>().unwrap(), key);
+        }
+        assert!("trade:1m:fUSD".parse::<CandleKey>().is_err());
+    }
+}
@@ -1,40 +1,204 @@
-use errors::*;
-use auth;
+use crate::errors::*;
+use crate::auth::{self, NonceProvider};
 use reqwest;
-use reqwest::{StatusCode, Response};
+use reqwest::StatusCode;
+use reqwest::blocking::Response;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT, CONTENT_TYPE};
-use std::io::Read;
+use crate::rate_limit::{endpoint_family, RateLimiter};
+use crate::retry::RetryPolicy;
+use std::fmt;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 use serde::Serialize;
 use log::*;
 
-static API1_HOST : &'static str = "https://api.bitfinex.com/v2/";
-static API_SIGNATURE_PATH : &'static str = "/api/v2/auth/r/";
-static API_SIGNATURE_PATH_W : &'static str = "/api/v2/auth/w/";
-static NO_PARAMS: &'static [(); 0] = &[];
+static API_HOST: &str = "https://api.bitfinex.com/v2/";
+static API_PUB_HOST: &str = "https://api-pub.bitfinex.com/v2/";
+static WEBSOCKET_URL: &str = "wss://api.bitfinex.com/ws/2";
+static DEFAULT_USER_AGENT: &str = "bitfinex-rs";
+pub(crate) static API_SIGNATURE_PATH: &str = "/api/v2/auth/r/";
+pub(crate) static API_SIGNATURE_PATH_W: &str = "/api/v2/auth/w/";
+pub(crate) static NO_PARAMS: &[(); 0] = &[];
+
+/// Connection settings shared by the REST `Client` and `WebSockets`.
+///
+/// The defaults point at the production Bitfinex endpoints; override them
+/// to run against a local mock server or a proxy.
+#[derive(Clone)]
+pub struct ClientConfig {
+    /// Host used for authenticated (`auth/r/`, `auth/w/`) requests
+    pub rest_host: String,
+
+    /// Host used for public requests
+    pub public_rest_host: String,
+
+    /// WebSocket endpoint
+    pub websocket_url: String,
+
+    /// Request timeout, `None` keeps the reqwest default
+    pub timeout: Option<Duration>,
+
+    /// Value sent in the `User-Agent` header
+    pub user_agent: String,
+
+    /// Retry behaviour on rate limits and transient failures
+    pub retry_policy: RetryPolicy,
+
+    /// Optional client-side limiter, shared by every client built from this config
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+
+    /// Nonce source for signed requests, the process wide default unless overridden
+    pub nonce_provider: Arc<dyn NonceProvider>,
+}
+
+impl fmt::Debug for ClientConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClientConfig")
+            .field("rest_host", &self.rest_host)
+            .field("public_rest_host", &self.public_rest_host)
+            .field("websocket_url", &self.websocket_url)
+            .field("timeout", &self.timeout)
+            .field("user_agent", &self.user_agent)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        ClientConfig {
+            rest_host: API_HOST.into(),
+            public_rest_host: API_PUB_HOST.into(),
+            websocket_url: WEBSOCKET_URL.into(),
+            timeout: None,
+            user_agent: DEFAULT_USER_AGENT.into(),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+            nonce_provider: auth::default_nonce_provider(),
+        }
+    }
+}
+
+impl ClientConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets both the public and the authenticated REST host.
+    pub fn host<S: Into<String>>(mut self, host: S) -> Self {
+        let host = with_trailing_slash(host.into());
+        self.public_rest_host = host.clone();
+        self.rest_host = host;
+        self
+    }
+
+    pub fn rest_host<S: Into<String>>(mut self, host: S) -> Self {
+        self.rest_host = with_trailing_slash(host.into());
+        self
+    }
+
+    pub fn public_rest_host<S: Into<String>>(mut self, host: S) -> Self {
+        self.public_rest_host = with_trailing_slash(host.into());
+        self
+    }
+
+    pub fn websocket_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.websocket_url = url.into();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(Arc::new(rate_limiter));
+        self
+    }
+
+    pub fn nonce_provider<N: NonceProvider + 'static>(mut self, nonce_provider: N) -> Self {
+        self.nonce_provider = Arc::new(nonce_provider);
+        self
+    }
+}
+
+fn with_trailing_slash(mut host: String) -> String {
+    if !host.ends_with('/') {
+        host.push('/');
+    }
+    host
+}
 
 #[derive(Clone)]
 pub struct Client {
     api_key: String,
-    secret_key: String
+    secret_key: String,
+    config: ClientConfig,
+    http: reqwest::blocking::Client,
 }
 
 impl Client {
     pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Client::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        let mut builder = reqwest::blocking::Client::builder();
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        let http = builder.build().expect("Failed to build the HTTP client");
+
         Client {
-            api_key : api_key.unwrap_or("".into()),
-            secret_key : secret_key.unwrap_or("".into())
+            api_key : api_key.unwrap_or_default(),
+            secret_key : secret_key.unwrap_or_default(),
+            config,
+            http,
         }
     }
 
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
     pub fn get(&self, endpoint: String, request: String) -> Result<String> {
-        let mut url: String = format!("{}{}", API1_HOST, endpoint);
-        if !request.is_empty() {
-            url.push_str(format!("?{}", request).as_str());
-        }
+        let url = public_url(&self.config, &endpoint, &request);
 
-        let response = reqwest::get(url.as_str())?;
+        self.execute(&endpoint_family("", &endpoint), false, || {
+            let response = self.http.get(url.as_str())
+                .header(USER_AGENT, HeaderValue::from_str(self.config.user_agent.as_str())?)
+                .send()?;
 
-        self.handler(response)
+            self.handler(response)
+        })
+    }
+
+    /// POST to a public endpoint, such as the `calc/*` ones.
+    pub fn post(&self, endpoint: String, request: String, payload: String) -> Result<String> {
+        let url = public_url(&self.config, &endpoint, &request);
+
+        self.execute(&endpoint_family("", &endpoint), false, || {
+            let response = self.http.post(url.as_str())
+                .header(USER_AGENT, HeaderValue::from_str(self.config.user_agent.as_str())?)
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .body(payload.clone())
+                .send()?;
+
+            self.handler(response)
+        })
     }
 
     pub fn post_signed(&self, request: String, payload: String) -> Result<String> {
@@ -47,16 +211,17 @@ impl Client {
         payload: String,
         params: &P,
     ) -> Result<String> {
-        let url: String = format!("{}auth/r/{}", API1_HOST, request);
+        let url: String = format!("{}auth/r/{}", self.config.rest_host, request);
 
-        let client = reqwest::Client::new();
-        let response = client.post(url.as_str())
-            .headers(self.build_headers(request, payload.clone())?)
-            .body(payload)
-            .query(params)
-            .send()?;
+        self.execute(&endpoint_family("auth/r/", &request), false, || {
+            let response = self.http.post(url.as_str())
+                .headers(self.build_headers(request.clone(), payload.clone())?)
+                .body(payload.clone())
+                .query(params)
+                .send()?;
 
-        self.handler(response)
+            self.handler(response)
+        })
     }
 
     pub fn post_w_signed(&self, request: String, payload: String) -> Result<String> {
@@ -69,80 +234,121 @@ impl Client {
         payload: String,
         params: &P,
     ) -> Result<String> {
-        let url: String = format!("{}auth/w/{}", API1_HOST, request);
-        let headers = self.build_w_headers(request, payload.clone())?;
-
-        let client = reqwest::Client::new();
-        trace!("URL: {}", url.as_str());
-        trace!("Headers: {:?}", headers);
-        let response = client.post(url.as_str())
-            .headers(headers)
-            .body(payload)
-            .query(params)
-            .send()?;
-        trace!("Response: {response:?}");
-        // info!("Response body: {}", response.text()?);
-        self.handler(response)
+        let url: String = format!("{}auth/w/{}", self.config.rest_host, request);
+
+        self.execute(&endpoint_family("auth/w/", &request), true, || {
+            let headers = self.build_w_headers(request.clone(), payload.clone())?;
+
+            trace!("URL: {}", url.as_str());
+            trace!("Headers: {:?}", headers);
+            let response = self.http.post(url.as_str())
+                .headers(headers)
+                .body(payload.clone())
+                .query(params)
+                .send()?;
+            trace!("Response: {response:?}");
+            self.handler(response)
+        })
+    }
+
+    /// Sends a request through the rate limiter, retrying it according to the retry policy.
+    /// Signed requests are rebuilt on every attempt so each one gets a fresh nonce.
+    fn execute<F>(&self, family: &str, write: bool, send: F) -> Result<String>
+        where F: Fn() -> Result<String>
+    {
+        let mut attempt = 0;
+        loop {
+            if let Some(ref limiter) = self.config.rate_limiter {
+                let wait = limiter.reserve(family);
+                if !wait.is_zero() {
+                    debug!("Rate limiter: waiting {:?} for {}", wait, family);
+                    thread::sleep(wait);
+                }
+            }
+
+            match send() {
+                Err(e) => match self.config.retry_policy.retry_delay(&e, attempt, write) {
+                    Some(delay) => {
+                        warn!("Request to {} failed ({}), retrying in {:?}", family, e, delay);
+                        thread::sleep(delay);
+                        attempt += 1;
+                    }
+                    None => return Err(e),
+                },
+                result => return result,
+            }
+        }
     }
 
     fn build_headers(&self, request: String, payload: String) -> Result<HeaderMap> {
-        let nonce: String = auth::generate_nonce()?;
-        let signature_path: String = format!("{}{}{}{}", API_SIGNATURE_PATH, request, nonce, payload);
+        signed_headers(&self.api_key, &self.secret_key, &self.config, API_SIGNATURE_PATH, &request, &payload)
+    }
 
-        let signature = auth::sign_payload(self.secret_key.as_bytes(), signature_path.as_bytes())?;
+    fn build_w_headers(&self, request: String, payload: String) -> Result<HeaderMap> {
+        signed_headers(&self.api_key, &self.secret_key, &self.config, API_SIGNATURE_PATH_W, &request, &payload)
+    }
 
-        let mut headers = HeaderMap::new();
-        headers.insert(USER_AGENT, HeaderValue::from_static("bitfinex-rs"));
-        headers.insert(HeaderName::from_static("bfx-nonce"), HeaderValue::from_str(nonce.as_str())?);
-        headers.insert(HeaderName::from_static("bfx-apikey"), HeaderValue::from_str(self.api_key.as_str())?);
-        headers.insert(HeaderName::from_static("bfx-signature"), HeaderValue::from_str(signature.as_str())?);
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    fn handler(&self, response: Response) -> Result<String> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text()?;
 
-        Ok(headers)
+        handle_response(status, &headers, body)
     }
 
-    fn build_w_headers(&self, request: String, payload: String) -> Result<HeaderMap> {
-        let nonce: String = auth::generate_nonce()?;
-        let signature_path: String = format!("{}{}{}{}", API_SIGNATURE_PATH_W, request, nonce, payload);
-
-        let signature = auth::sign_payload(self.secret_key.as_bytes(), signature_path.as_bytes())?;
-
-        let mut headers = HeaderMap::new();
-        headers.insert(USER_AGENT, HeaderValue::from_static("bitfinex-rs"));
-        headers.insert(HeaderName::from_static("bfx-nonce"), HeaderValue::from_str(nonce.as_str())?);
-        headers.insert(HeaderName::from_static("bfx-apikey"), HeaderValue::from_str(self.api_key.as_str())?);
-        headers.insert(HeaderName::from_static("bfx-signature"), HeaderValue::from_str(signature.as_str())?);
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-        Ok(headers)
-    }
-
-    fn handler(&self, mut response: Response) -> Result<String> {
-        match response.status() {
-            StatusCode::OK => {
-                let mut body = String::new();
-                response.read_to_string(&mut body)?;
-                return Ok(body);
-            },
-            StatusCode::INTERNAL_SERVER_ERROR => {
-                let mut body = String::new();
-                response.read_to_string(&mut body)?;
-                error!("Returned response: {body}");
-                bail!("Internal Server Error");
-            }
-            StatusCode::SERVICE_UNAVAILABLE => {
-                bail!("Service Unavailable");
-            }
-            StatusCode::UNAUTHORIZED => {
-                bail!("Unauthorized");
-            }
-            StatusCode::BAD_REQUEST => {
-                bail!(format!("Bad Request: {:?}", response));
-            }
-            s => {
-                bail!(format!("Received response: {:?}", s));
-            }
-        };
+}
+
+pub(crate) fn public_url(config: &ClientConfig, endpoint: &str, request: &str) -> String {
+    let mut url: String = format!("{}{}", config.public_rest_host, endpoint);
+    if !request.is_empty() {
+        url.push_str(format!("?{}", request).as_str());
+    }
+    url
+}
+
+pub(crate) fn signed_headers(
+    api_key: &str,
+    secret_key: &str,
+    config: &ClientConfig,
+    signature_prefix: &str,
+    request: &str,
+    payload: &str,
+) -> Result<HeaderMap> {
+    let nonce: String = config.nonce_provider.next_nonce()?.to_string();
+    let signature_path: String = format!("{}{}{}{}", signature_prefix, request, nonce, payload);
+
+    let signature = auth::sign_payload(secret_key.as_bytes(), signature_path.as_bytes())?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_str(config.user_agent.as_str())?);
+    headers.insert(HeaderName::from_static("bfx-nonce"), HeaderValue::from_str(nonce.as_str())?);
+    headers.insert(HeaderName::from_static("bfx-apikey"), HeaderValue::from_str(api_key)?);
+    headers.insert(HeaderName::from_static("bfx-signature"), HeaderValue::from_str(signature.as_str())?);
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    Ok(headers)
+}
+
+pub(crate) fn handle_response(status: StatusCode, headers: &HeaderMap, body: String) -> Result<String> {
+    match status {
+        StatusCode::OK => {
+            Ok(body)
+        },
+        s => {
+            error!("Returned response {}: {}", s, body);
+            Err(Error::from_response(s, headers, body))
+        }
     }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_host_adds_trailing_slash() {
+        let config = ClientConfig::new().host("http://127.0.0.1:8080/v2");
+        assert_eq!(config.rest_host, "http://127.0.0.1:8080/v2/");
+        assert_eq!(config.public_rest_host, "http://127.0.0.1:8080/v2/");
+    }
 }
@@ -0,0 +1,315 @@
+use crate::client::*;
+use crate::errors::*;
+use crate::number::{self, Number};
+use crate::symbol::TradingPair;
+use log::*;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlatformStatus {
+    Maintenance,
+    Operative,
+}
+
+/// Entry of `pub:info:pair`, order size limits and margin requirements of a pair.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PairInfo {
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+
+    #[serde(deserialize_with = "number::string_or_number")]
+    pub min_order_size: Number,
+    #[serde(deserialize_with = "number::string_or_number")]
+    pub max_order_size: Number,
+
+    #[serde(skip_serializing, default)]
+    _placeholder_4: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_5: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_6: Option<Value>,
+
+    #[serde(default)]
+    pub initial_margin: Option<Number>,
+    #[serde(default)]
+    pub min_margin: Option<Number>,
+}
+
+/// Entry of `pub:map:currency:tx:fee`, the fee charged on withdrawals.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WithdrawalFee {
+    #[serde(skip_serializing)]
+    _placeholder: Option<Value>,
+    pub fee: Number,
+}
+
+/// Tier of the `pub:fees` schedule, read from `[VOLUME, MAKER_FEE, TAKER_FEE, ...]` entries.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeeTier {
+    /// 30 day trading volume (USD) from which the tier applies
+    pub volume: Number,
+    pub maker_fee: Number,
+    pub taker_fee: Number,
+}
+
+/// The `pub:map:currency:*` mappings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurrencyMap {
+    /// Currency code to its full name ("BTC" => "Bitcoin")
+    Label,
+    /// Currency code to the symbol shown in the UI ("UST" => "USDt")
+    Symbol,
+    /// Currency code to its unit
+    Unit,
+    /// Derivatives currency to its underlying
+    Underlying,
+    /// Currency code to the network it belongs to
+    Pool,
+    /// Currency code to its block explorer urls
+    Explorer,
+    /// Currency code to its withdrawal fee
+    TxFee,
+}
+
+impl CurrencyMap {
+    pub fn key(&self) -> &'static str {
+        match *self {
+            CurrencyMap::Label => "pub:map:currency:label",
+            CurrencyMap::Symbol => "pub:map:currency:sym",
+            CurrencyMap::Unit => "pub:map:currency:unit",
+            CurrencyMap::Underlying => "pub:map:currency:undl",
+            CurrencyMap::Pool => "pub:map:currency:pool",
+            CurrencyMap::Explorer => "pub:map:currency:explorer",
+            CurrencyMap::TxFee => "pub:map:currency:tx:fee",
+        }
+    }
+}
+
+/// Platform status and the `conf/pub:*` configuration endpoints.
+#[derive(Clone)]
+pub struct Config {
+    client: Client,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Config::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Config::with_client(Client::with_config(None, None, config))
+    }
+
+    /// Shares the connection pool of another sub-API.
+    pub(crate) fn with_client(client: Client) -> Self {
+        Config { client }
+    }
+
+    pub fn platform_status(&self) -> Result<PlatformStatus> {
+        let data = self.client.get("platform/status".into(), String::new())?;
+
+        platform_status(decode(data.as_str())?)
+    }
+
+    pub fn exchange_pairs(&self) -> Result<Vec<TradingPair>> {
+        self.conf_list("pub:list:pair:exchange")
+    }
+
+    pub fn margin_pairs(&self) -> Result<Vec<TradingPair>> {
+        self.conf_list("pub:list:pair:margin")
+    }
+
+    pub fn futures_pairs(&self) -> Result<Vec<TradingPair>> {
+        self.conf_list("pub:list:pair:futures")
+    }
+
+    pub fn currencies(&self) -> Result<Vec<String>> {
+        self.conf_list("pub:list:currency")
+    }
+
+    pub fn currency_labels(&self) -> Result<HashMap<String, String>> {
+        self.currency_map(CurrencyMap::Label)
+    }
+
+    pub fn currency_symbols(&self) -> Result<HashMap<String, String>> {
+        self.currency_map(CurrencyMap::Symbol)
+    }
+
+    pub fn withdrawal_fees(&self) -> Result<HashMap<String, WithdrawalFee>> {
+        self.currency_map(CurrencyMap::TxFee)
+    }
+
+    /// Any of the `pub:map:currency:*` mappings, keyed by currency code.
+    pub fn currency_map<T: DeserializeOwned>(&self, map: CurrencyMap) -> Result<HashMap<String, T>> {
+        self.conf_list::<(String, T)>(map.key()).map(to_map)
+    }
+
+    /// Minimum and maximum order sizes of the exchange and margin pairs.
+    pub fn pair_info(&self) -> Result<HashMap<TradingPair, PairInfo>> {
+        self.conf_list::<(TradingPair, PairInfo)>("pub:info:pair").map(to_map)
+    }
+
+    /// Minimum and maximum order sizes of the derivatives pairs.
+    pub fn futures_pair_info(&self) -> Result<HashMap<TradingPair, PairInfo>> {
+        self.conf_list::<(TradingPair, PairInfo)>("pub:info:pair:futures").map(to_map)
+    }
+
+    /// Deposit and withdrawal methods, mapped to the currencies they move.
+    pub fn tx_methods(&self) -> Result<HashMap<String, Vec<String>>> {
+        self.conf_list::<(String, Vec<String>)>(TX_METHODS).map(to_map)
+    }
+
+    /// Method moving `currency`, lowercased as expected by `deposit/address`.
+    /// Fails for currencies available on several networks (e.g. UST), listing
+    /// the methods to pick from.
+    pub fn tx_method(&self, currency: &str) -> Result<Option<String>> {
+        find_tx_method(self.conf_list(TX_METHODS)?, currency)
+    }
+
+    /// Trading fee tiers, from the lowest trading volume up.
+    pub fn fees(&self) -> Result<Vec<FeeTier>> {
+        self.conf(FEES).map(fee_tiers)
+    }
+
+    /// Fetches a `conf/{key}` list, skipping the entries that can't be parsed.
+    pub fn conf_list<T: DeserializeOwned>(&self, key: &str) -> Result<Vec<T>> {
+        self.conf(key).map(|entries| parse_entries(key, entries))
+    }
+
+    /// Fetches a single `conf/{key}` entry, e.g. "pub:list:pair:exchange".
+    pub fn conf<T: DeserializeOwned>(&self, key: &str) -> Result<T> {
+        let data = self.client.get(format!("conf/{}", key), String::new())?;
+
+        first(key, decode(data.as_str())?)
+    }
+}
+
+pub(crate) static TX_METHODS: &str = "pub:map:tx:method";
+pub(crate) static FEES: &str = "pub:fees";
+
+pub(crate) fn find_tx_method(methods: Vec<(String, Vec<String>)>, currency: &str) -> Result<Option<String>> {
+    let mut matching: Vec<String> = methods
+        .into_iter()
+        .filter(|(_, currencies)| currencies.iter().any(|c| c == currency))
+        .map(|(method, _)| method.to_lowercase())
+        .collect();
+
+    if matching.len() > 1 {
+        return Err(Error::InvalidRequest(format!(
+            "{} moves over several methods, pick one of {}",
+            currency,
+            matching.join(", ")
+        )));
+    }
+
+    Ok(matching.pop())
+}
+
+pub(crate) fn platform_status(status: Vec<i64>) -> Result<PlatformStatus> {
+    match status.first() {
+        Some(1) => Ok(PlatformStatus::Operative),
+        Some(0) => Ok(PlatformStatus::Maintenance),
+        _ => Err(Error::Other(format!("Unexpected platform status {:?}", status))),
+    }
+}
+
+/// `conf` answers wrap the value of each requested key in an outer array.
+pub(crate) fn first<T>(key: &str, values: Vec<T>) -> Result<T> {
+    values.into_iter().next().ok_or_else(|| Error::Other(format!("Empty response for conf/{}", key)))
+}
+
+/// Decodes the entries of a `conf` list one by one. Entries that can't be parsed are
+/// skipped rather than failing the whole list.
+pub(crate) fn parse_entries<T: DeserializeOwned>(key: &str, entries: Vec<Value>) -> Vec<T> {
+    entries
+        .into_iter()
+        .filter_map(|entry| match T::deserialize(&entry) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                warn!("Skipping entry {} of conf/{}: {}", entry, key, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Fee tiers, ignoring the fields past the taker fee.
+pub(crate) fn fee_tiers(entries: Vec<Value>) -> Vec<FeeTier> {
+    let entries = entries
+        .into_iter()
+        .map(|entry| match entry {
+            Value::Array(mut fields) => {
+                fields.truncate(3);
+                Value::Array(fields)
+            }
+            other => other,
+        })
+        .collect();
+
+    parse_entries(FEES, entries)
+}
+
+pub(crate) fn to_map<K: Eq + Hash, T>(entries: Vec<(K, T)>) -> HashMap<K, T> {
+    entries.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pairs::BTCUSD;
+    use serde_json::from_str;
+
+    #[test]
+    fn test_pair_info() {
+        let data = "[[[\"BTCUSD\",[null,null,null,\"0.00006\",\"2000.0\",null,null,null,0.2,0.1]],[\"TESTBTC:TESTUSD\",[null,null,null,\"0.0002\",\"100.0\",null,null,null]]]]";
+        let info: HashMap<TradingPair, PairInfo> = to_map(parse_entries("", first("", from_str(data).expect("parsed")).unwrap()));
+        assert_eq!(info[&BTCUSD].max_order_size, "2000".parse::<Number>().unwrap());
+        assert!(info[&TradingPair::new("TESTBTC", "TESTUSD")].initial_margin.is_none());
+
+        let data = "[[\"BTCUSD\",\"ETHUSD\",\"btc-usd\",42]]";
+        let pairs: Vec<TradingPair> = parse_entries("", first("", from_str(data).expect("parsed")).unwrap());
+        assert_eq!(pairs, [BTCUSD, TradingPair::new("ETH", "USD")]);
+    }
+
+    #[test]
+    fn test_fee_tiers() {
+        let data = "[[[0,0.001,0.002],[500000,0.0008,0.002,null],\"n/a\"]]";
+        let tiers = fee_tiers(first(FEES, from_str(data).expect("parsed")).unwrap());
+        assert_eq!(tiers.len(), 2);
+        assert_eq!(tiers[1].volume, "500000".parse::<Number>().unwrap());
+        assert_eq!(tiers[1].maker_fee, "0.0008".parse::<Number>().unwrap());
+    }
+
+    #[test]
+    fn test_currency_map() {
+        let data = "[[[\"BTC\",[0,0.0004]],[\"ETH\",[0,0.00135]]]]";
+        let fees: Vec<(String, WithdrawalFee)> = first("", from_str(data).expect("parsed")).unwrap();
+        assert_eq!(fees[1].1.fee, "0.00135".parse::<Number>().unwrap());
+
+        assert_eq!(platform_status(vec![1]).unwrap(), PlatformStatus::Operative);
+    }
+
+    #[test]
+    fn test_tx_method() {
+        let data = "[[[\"BITCOIN\",[\"BTC\"]],[\"LNX\",[\"LNX\"]],[\"TETHERUSE\",[\"UST\"]],[\"TETHERUSX\",[\"UST\"]]]]";
+        let methods: Vec<(String, Vec<String>)> = first(TX_METHODS, from_str(data).expect("parsed")).unwrap();
+        assert_eq!(find_tx_method(methods.clone(), "LNX").unwrap().as_deref(), Some("lnx"));
+        assert_eq!(find_tx_method(methods.clone(), "XYZ").unwrap(), None);
+
+        let err = find_tx_method(methods, "UST").unwrap_err();
+        assert!(err.to_string().contains("tetheruse, tetherusx"));
+    }
+}
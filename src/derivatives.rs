@@ -0,0 +1,292 @@
+use crate::client::*;
+use crate::errors::*;
+use crate::number::Number;
+use crate::symbol::TradingPair;
+use log::*;
+use serde::de;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Status of a derivatives pair, as returned by `status/deriv` and the
+/// `deriv:*` websocket status channel.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DerivStatus {
+    pub mts: i64,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    pub deriv_price: Number,
+    pub spot_price: Number,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    pub insurance_fund_balance: Option<Number>,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    /// Timestamp of the next funding event (ms)
+    pub next_funding_evt_mts: Option<i64>,
+    /// Funding accrued so far for the next funding event
+    pub next_funding_accrued: Option<f64>,
+    /// Incremental accrual counter
+    pub next_funding_step: Option<i64>,
+    #[serde(skip_serializing)]
+    _placeholder_4: Option<Value>,
+    /// Funding applied in the current funding event
+    pub current_funding: Option<f64>,
+    #[serde(skip_serializing)]
+    _placeholder_5: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_6: Option<Value>,
+    pub mark_price: Option<Number>,
+    #[serde(skip_serializing)]
+    _placeholder_7: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_8: Option<Value>,
+    /// Total number of outstanding derivative contracts
+    pub open_interest: Option<Number>,
+    #[serde(skip_serializing)]
+    _placeholder_9: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_10: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_11: Option<Value>,
+    /// Range in which the funding rate is clamped
+    #[serde(default)]
+    pub clamp_min: Option<f64>,
+    #[serde(default)]
+    pub clamp_max: Option<f64>,
+}
+
+/// Forced position close, as returned by `liquidations/hist` and the
+/// `liq:global` websocket status channel.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Liquidation {
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    pub position_id: i64,
+    pub mts: i64,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    pub symbol: TradingPair,
+    pub amount: Number,
+    pub base_price: Number,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    /// 1 when the position was matched in the order book
+    pub is_match: i32,
+    /// 1 when the position was sold at market price
+    pub is_market_sold: i32,
+    #[serde(skip_serializing)]
+    _placeholder_4: Option<Value>,
+    pub liquidation_price: Option<Number>,
+}
+
+/// Key of a websocket `status` channel.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StatusKey {
+    /// `deriv:tBTCF0:USTF0`, status updates of a derivatives pair
+    Deriv(TradingPair),
+    /// `liq:global`, every liquidation on the platform
+    Liquidations,
+}
+
+impl fmt::Display for StatusKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StatusKey::Deriv(ref pair) => write!(f, "deriv:{}", pair),
+            StatusKey::Liquidations => f.write_str("liq:global"),
+        }
+    }
+}
+
+impl FromStr for StatusKey {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.split_once(':') {
+            Some(("deriv", pair)) => pair.parse().map(StatusKey::Deriv),
+            Some(("liq", "global")) => Ok(StatusKey::Liquidations),
+            _ => Err(Error::InvalidSymbol(value.to_owned())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for StatusKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatusHistoryParams {
+    /// Number of records requested (Max: 5000 for status, 500 for liquidations)
+    pub limit: Option<i32>,
+
+    /// Filter start (ms)
+    pub start: Option<i64>,
+
+    /// Filter end (ms)
+    pub end: Option<i64>,
+
+    /// Sorts the results from old > new
+    pub sort: Option<bool>,
+}
+
+impl StatusHistoryParams {
+    pub fn new() -> Self {
+        Self {
+            limit: Some(100),
+            sort: Some(false),
+            start: None,
+            end: None,
+        }
+    }
+
+    pub fn to_query(&self) -> String {
+        format!("{}={}&{}={}&{}={}&{}={}",
+            "limit", self.limit
+                         .map(|a| a.to_string())
+                         .unwrap_or_default(),
+            "start", self.start
+                         .map(|a| a.to_string())
+                         .unwrap_or_default(),
+            "end", self.end
+                       .map(|a| a.to_string())
+                       .unwrap_or_default(),
+            "sort", self.sort
+                        .map(|a| if a { "1" } else { "-1" })
+                        .unwrap_or(""),
+        )
+    }
+}
+
+/// Derivatives status and liquidation history.
+#[derive(Clone)]
+pub struct Derivatives {
+    client: Client,
+}
+
+impl Default for Derivatives {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Derivatives {
+    pub fn new() -> Self {
+        Derivatives::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Derivatives {
+            client: Client::with_config(None, None, config),
+        }
+    }
+
+    /// Current status of the given derivatives pairs, every pair when empty.
+    pub fn status(&self, pairs: &[TradingPair]) -> Result<HashMap<TradingPair, DerivStatus>> {
+        let data = self.client.get("status/deriv".into(), status_query(pairs))?;
+
+        parse_status(decode(data.as_str())?)
+    }
+
+    pub fn status_history<P>(&self, pair: P, params: &StatusHistoryParams) -> Result<Vec<DerivStatus>>
+        where P: TryInto<TradingPair>, Error: From<P::Error>
+    {
+        let endpoint: String = format!("status/deriv/{}/hist", pair.try_into()?);
+        let data = self.client.get(endpoint, params.to_query())?;
+
+        let history: Vec<DerivStatus> = decode(data.as_str())?;
+
+        Ok(history)
+    }
+
+    pub fn liquidations(&self, params: &StatusHistoryParams) -> Result<Vec<Liquidation>> {
+        let data = self.client.get("liquidations/hist".into(), params.to_query())?;
+
+        let liquidations: Vec<Vec<Liquidation>> = decode(data.as_str())?;
+
+        Ok(liquidations.into_iter().flatten().collect())
+    }
+}
+
+pub(crate) fn status_query(pairs: &[TradingPair]) -> String {
+    let keys = if pairs.is_empty() {
+        "ALL".to_owned()
+    } else {
+        pairs.iter().map(TradingPair::to_string).collect::<Vec<_>>().join(",")
+    };
+
+    format!("keys={}", keys)
+}
+
+/// Splits the `[KEY, ...status]` entries of a `status/deriv` answer. Entries whose
+/// key or fields can't be parsed are skipped rather than failing the whole list.
+pub(crate) fn parse_status(entries: Vec<Vec<Value>>) -> Result<HashMap<TradingPair, DerivStatus>> {
+    let mut status = HashMap::with_capacity(entries.len());
+
+    for mut entry in entries {
+        if entry.is_empty() {
+            continue;
+        }
+
+        let raw_key = entry.remove(0);
+        let pair: TradingPair = match raw_key.as_str().map(str::parse) {
+            Some(Ok(pair)) => pair,
+            _ => {
+                warn!("Skipping derivatives status with unexpected key {}", raw_key);
+                continue;
+            }
+        };
+
+        match serde_json::from_value(Value::Array(entry)) {
+            Ok(entry) => {
+                status.insert(pair, entry);
+            }
+            Err(e) => warn!("Skipping derivatives status of {} with unexpected shape: {}", pair, e),
+        }
+    }
+
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::from_str;
+
+    #[test]
+    fn test_deriv_status() {
+        let data = "[[\"tBTCF0:USTF0\",1605788850000,null,18315.5,18309.4,null,17283.82,null,1605801600000,0.00005471,9,null,0.00001012,null,null,18312.16,null,null,2431.54,null,null,null,0.005,0.01]]";
+        let status = parse_status(from_str(data).expect("parsed")).unwrap();
+
+        let btc = &status[&TradingPair::new("BTCF0", "USTF0")];
+        assert_eq!(btc.next_funding_evt_mts, Some(1605801600000));
+        assert_eq!(btc.clamp_max, Some(0.01));
+        assert_eq!(btc.open_interest, Some("2431.54".parse::<Number>().unwrap()));
+        assert_eq!(status_query(&[]), "keys=ALL");
+    }
+
+    #[test]
+    fn test_deriv_status_skips_malformed_entries() {
+        let data = "[[\"tBTCF0:USTF0\",1605788850000,null,18315.5,18309.4,null,17283.82,null,1605801600000,0.00005471,9,null,0.00001012,null,null,18312.16,null,null,2431.54,null,null,null,0.005,0.01],[\"tETHF0:USTF0\",\"halted\"]]";
+        let status = parse_status(from_str(data).expect("parsed")).unwrap();
+
+        assert_eq!(status.len(), 1);
+        assert!(status.contains_key(&TradingPair::new("BTCF0", "USTF0")));
+    }
+
+    #[test]
+    fn test_liquidations() {
+        let data = "[[[\"pos\",145400868,1609144352338,null,\"tBTCF0:USTF0\",-0.001,28104,null,1,1,null,33331.7]]]";
+        let liquidations: Vec<Vec<Liquidation>> = from_str(data).expect("parsed");
+        assert_eq!(liquidations[0][0].position_id, 145400868);
+        assert_eq!(liquidations[0][0].is_market_sold, 1);
+
+        let key: StatusKey = "deriv:tBTCF0:USTF0".parse().unwrap();
+        assert_eq!(key, StatusKey::Deriv(TradingPair::new("BTCF0", "USTF0")));
+        assert_eq!(StatusKey::Liquidations.to_string(), "liq:global");
+    }
+}
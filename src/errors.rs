@@ -1,30 +1,197 @@
-use std;
-use reqwest;
-use url;
-use serde_json;
-use tungstenite;
-
-error_chain! {
-    types {
-        Error, ErrorKind, ResultExt, Result;
+use std::time::Duration;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde_json::{self, Value};
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+static ERR_RATE_LIMIT: &str = "ERR_RATE_LIMIT";
+static NONCE_SMALL: &str = "nonce: small";
+static ERR_AUTH_FAIL: i64 = 10100;
+static ERR_RATE_LIMIT_CODE: i64 = 11010;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    /// `["error", code, "message"]` answer that doesn't map to a more specific variant
+    #[error("Bitfinex API error {code}: {message}")]
+    Api { code: i64, message: String },
+
+    /// HTTP 429 or `ERR_RATE_LIMIT`; `retry_after` comes from the `Retry-After` header
+    #[error("Rate limited")]
+    RateLimited { retry_after: Option<Duration> },
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// The nonce was not greater than the last one used with this API key
+    #[error("Nonce too small")]
+    NonceTooSmall,
+
+    /// Non-200 response without a Bitfinex error body
+    #[error("Received response {status}: {body}")]
+    Status { status: u16, body: String },
+
+    /// Transport level failure (connection, TLS, timeout)
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+
+    #[error("Failed to decode response: {source}")]
+    Decode {
+        body: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error(transparent)]
+    WebSocket(Box<tungstenite::Error>),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    InvalidHeader(#[from] reqwest::header::InvalidHeaderValue),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+
+    #[error(transparent)]
+    Timestamp(#[from] std::time::SystemTimeError),
+
+    /// String that is not a valid `t`/`f` symbol
+    #[error("Invalid symbol: {0}")]
+    InvalidSymbol(String),
+
+    /// A request was rejected locally before being sent
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<tungstenite::Error> for Error {
+    fn from(e: tungstenite::Error) -> Self {
+        Error::WebSocket(Box::new(e))
     }
+}
+
+impl From<std::convert::Infallible> for Error {
+    fn from(e: std::convert::Infallible) -> Self {
+        match e {}
+    }
+}
 
-    errors {         
-        Internal(t: String) {
-            description("invalid toolchain name")
-            display("invalid toolchain name: '{}'", t)
+impl Error {
+    /// Whether the request may succeed if sent again: rate limits, stale nonces,
+    /// timeouts, connection failures and 5xx answers without an API error body.
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            Error::RateLimited { .. } | Error::NonceTooSmall => true,
+            Error::Http(ref e) => e.is_timeout() || e.is_connect(),
+            Error::Status { status, .. } => matches!(status, 500 | 502 | 503 | 504),
+            _ => false,
         }
     }
 
-    foreign_links {
-        ReqError(reqwest::Error);
-        InvalidHeaderError(reqwest::header::InvalidHeaderValue);
-        IoError(std::io::Error);
-        ParseFloatError(std::num::ParseFloatError);
-        UrlParserError(url::ParseError);
-        Json(serde_json::Error);
-        Tungstenite(tungstenite::Error);
-        TimestampError(std::time::SystemTimeError);
+    /// Builds an error from a non-200 REST response, decoding the Bitfinex
+    /// `["error", code, "message"]` body when present.
+    pub(crate) fn from_response(status: StatusCode, headers: &HeaderMap, body: String) -> Self {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Error::RateLimited { retry_after: retry_after(headers) };
+        }
+
+        match api_error(&body) {
+            Some((code, message)) => {
+                if code == ERR_RATE_LIMIT_CODE || message.contains(ERR_RATE_LIMIT) {
+                    Error::RateLimited { retry_after: retry_after(headers) }
+                } else if message.contains(NONCE_SMALL) {
+                    Error::NonceTooSmall
+                } else if code == ERR_AUTH_FAIL || status == StatusCode::UNAUTHORIZED {
+                    Error::Unauthorized(message)
+                } else {
+                    Error::Api { code, message }
+                }
+            }
+            None if status == StatusCode::UNAUTHORIZED => Error::Unauthorized(body),
+            None => Error::Status { status: status.as_u16(), body },
+        }
     }
+}
 
+fn api_error(body: &str) -> Option<(i64, String)> {
+    match serde_json::from_str::<Vec<Value>>(body).ok()?.as_slice() {
+        [Value::String(tag), code, message, ..] if tag == "error" => {
+            Some((code.as_i64().unwrap_or_default(), message.as_str().unwrap_or_default().to_owned()))
+        }
+        _ => None,
+    }
+}
+
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Deserializes a response body, keeping the body in the error on failure.
+pub(crate) fn decode<T: DeserializeOwned>(body: &str) -> Result<T> {
+    parse(body).map_err(|source| Error::Decode { body: body.to_owned(), source })
+}
+
+/// With the `decimal` feature serde_json keeps numbers exactly as written, which untagged
+/// enums such as `DataEvent` can only deserialize into `i64`/`f64` through a `Value`.
+#[cfg(feature = "decimal")]
+fn parse<T: DeserializeOwned>(body: &str) -> serde_json::Result<T> {
+    serde_json::from_str::<Value>(body).and_then(serde_json::from_value)
+}
+
+#[cfg(not(feature = "decimal"))]
+fn parse<T: DeserializeOwned>(body: &str) -> serde_json::Result<T> {
+    serde_json::from_str(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_body() {
+        let headers = HeaderMap::new();
+
+        let err = Error::from_response(StatusCode::INTERNAL_SERVER_ERROR, &headers, "[\"error\",10114,\"nonce: small\"]".into());
+        assert!(matches!(err, Error::NonceTooSmall));
+
+        let err = Error::from_response(StatusCode::INTERNAL_SERVER_ERROR, &headers, "[\"error\",10100,\"apikey: invalid\"]".into());
+        assert!(matches!(err, Error::Unauthorized(_)));
+
+        let err = Error::from_response(StatusCode::INTERNAL_SERVER_ERROR, &headers, "[\"error\",11010,\"ERR_RATE_LIMIT\"]".into());
+        assert!(matches!(err, Error::RateLimited { .. }));
+
+        let err = Error::from_response(StatusCode::INTERNAL_SERVER_ERROR, &headers, "[\"error\",10020,\"symbol: invalid\"]".into());
+        assert!(matches!(err, Error::Api { code: 10020, .. }));
+
+        let err = Error::from_response(StatusCode::BAD_GATEWAY, &headers, "<html></html>".into());
+        assert!(matches!(err, Error::Status { status: 502, .. }));
+    }
+
+    #[test]
+    fn test_rate_limit_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "60".parse().unwrap());
+
+        match Error::from_response(StatusCode::TOO_MANY_REQUESTS, &headers, String::new()) {
+            Error::RateLimited { retry_after } => assert_eq!(retry_after, Some(Duration::from_secs(60))),
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
 }
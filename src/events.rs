@@ -1,7 +1,8 @@
-use ticker::*;
-use candles::Candle;
-use trades::{TradingPair as TradesTradingPair, FundingCurrency as TradesFundingCurrency};
-use book::{TradingPair as BookTradingPair, FundingCurrency as BookFundingCurrency, RawBook};
+use crate::ticker::*;
+use crate::candles::Candle;
+use crate::derivatives::{DerivStatus, Liquidation, StatusKey};
+use crate::trades::{TradingPair as TradesTradingPair, FundingCurrency as TradesFundingCurrency};
+use crate::book::{TradingPair as BookTradingPair, FundingCurrency as BookFundingCurrency, RawBook};
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -11,6 +12,7 @@ pub enum NotificationEvent {
     Info(InfoMessage),
     TradingSubscribed(TradingSubscriptionMessage),
     FundingSubscribed(FundingSubscriptionMessage),
+    StatusSubscribed(StatusSubscriptionMessage),
     CandlesSubscribed(CandlesSubscriptionMessage),
     RawBookSubscribed(RawBookSubscriptionMessage),
 }
@@ -32,9 +34,20 @@ pub enum DataEvent {
     RawBookUpdateEvent (i32, Vec<RawBook>),
     CandlesSnapshotEvent (i32, Vec<Candle>),
     CandlesUpdateEvent (i32, Candle),
+    DerivStatusEvent (i32, Box<DerivStatus>),
+    LiquidationEvent (i32, Vec<Liquidation>),
     HeartbeatEvent (i32, String)
 }
 
+/// A decoded websocket frame, one variant per `EventHandler` callback.
+#[derive(Debug)]
+pub enum WsEvent {
+    Connected(NotificationEvent),
+    Auth(NotificationEvent),
+    Subscribed(NotificationEvent),
+    Data(DataEvent),
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthMessage {
@@ -96,6 +109,17 @@ pub struct CandlesSubscriptionMessage {
     pub key: String
 }
 
+/// Subscription to a `status` channel. Listed before `CandlesSubscribed`, which
+/// has the same fields, so that only keys parsing as a `StatusKey` end up here.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusSubscriptionMessage {
+    pub event: String,
+    pub channel: String,
+    pub chan_id: u32,
+    pub key: StatusKey
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RawBookSubscriptionMessage {
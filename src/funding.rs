@@ -0,0 +1,376 @@
+use crate::client::*;
+use crate::errors::*;
+use crate::notification::Notification;
+use crate::number::{self, Number};
+use crate::orders::OrderFlags;
+use crate::symbol::{FundingCurrency, TradingPair};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use log::*;
+use std::fmt;
+
+/// Shortest and longest period a funding offer can be made for, in days.
+pub static MIN_PERIOD: u32 = 2;
+pub static MAX_PERIOD: u32 = 120;
+
+string_enum! {
+    pub enum FundingOfferType {
+        /// Fixed rate
+        Limit => "LIMIT",
+        /// Variable rate, following the FRR plus the given delta
+        FrrDeltaVar => "FRRDELTAVAR",
+        /// Fixed rate, set to the FRR plus the given delta at execution
+        FrrDeltaFix => "FRRDELTAFIX",
+    }
+}
+
+string_enum! {
+    /// Status of a funding offer, loan or credit.
+    pub enum FundingStatus {
+        Active => "ACTIVE",
+        Executed => "EXECUTED",
+        PartiallyFilled => "PARTIALLY FILLED",
+        Canceled => "CANCELED",
+        Closed => "CLOSED",
+    }
+}
+
+string_enum! {
+    pub enum FundingRateType {
+        Fixed => "FIXED",
+        Var => "VAR",
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FundingOffer {
+    pub id: i64,
+    pub symbol: FundingCurrency,
+    pub mts_create: i64,
+    pub mts_update: i64,
+    /// Amount still on offer
+    pub amount: Number,
+    pub amount_orig: Number,
+    pub offer_type: FundingOfferType,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    pub flags: Option<OrderFlags>,
+    pub status: FundingStatus,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_4: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_5: Option<Value>,
+    pub rate: f64,
+    /// Period in days
+    pub period: u32,
+    #[serde(deserialize_with = "number::flag")]
+    pub notify: bool,
+    #[serde(deserialize_with = "number::flag")]
+    pub hidden: bool,
+    #[serde(skip_serializing)]
+    _placeholder_6: Option<Value>,
+    #[serde(deserialize_with = "number::flag")]
+    pub renew: bool,
+    #[serde(skip_serializing, default)]
+    _placeholder_7: Option<Value>,
+}
+
+/// Funds taken or provided, returned by `funding/credits` (funds used in a
+/// position) and `funding/loans`, which leave `position_pair` empty.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FundingCredit {
+    pub id: i64,
+    pub symbol: FundingCurrency,
+    /// 1 when lending, -1 when borrowing, 0 when both
+    pub side: i32,
+    pub mts_create: i64,
+    pub mts_update: i64,
+    pub amount: Number,
+    pub flags: Option<OrderFlags>,
+    pub status: FundingStatus,
+    pub rate_type: Option<FundingRateType>,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    pub rate: f64,
+    /// Period in days
+    pub period: u32,
+    pub mts_opening: Option<i64>,
+    pub mts_last_payout: Option<i64>,
+    #[serde(deserialize_with = "number::flag")]
+    pub notify: bool,
+    #[serde(deserialize_with = "number::flag")]
+    pub hidden: bool,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    #[serde(deserialize_with = "number::flag")]
+    pub renew: bool,
+    #[serde(skip_serializing)]
+    _placeholder_4: Option<Value>,
+    /// Set when the funding can't be returned before the end of its period
+    #[serde(deserialize_with = "number::flag")]
+    pub no_close: bool,
+    #[serde(default)]
+    pub position_pair: Option<TradingPair>,
+}
+
+pub type FundingLoan = FundingCredit;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FundingTrade {
+    pub id: i64,
+    pub symbol: FundingCurrency,
+    pub mts_create: i64,
+    pub offer_id: i64,
+    /// Positive when borrowed, negative when lent
+    pub amount: Number,
+    pub rate: f64,
+    pub period: u32,
+    #[serde(skip_serializing, default)]
+    _placeholder_1: Option<Value>,
+}
+
+pub type FundingOfferNotification = Notification<FundingOffer>;
+/// `funding/offer/cancel/all` carries no data, only the status and text.
+pub type FundingCancelAllNotification = Notification<Option<Value>>;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SubmitFundingOffer {
+    #[serde(rename = "type")]
+    offer_type: FundingOfferType,
+    symbol: FundingCurrency,
+    amount: String,
+    rate: String,
+    period: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flags: Option<OrderFlags>,
+}
+
+impl SubmitFundingOffer {
+    /// Offers `amount` at `rate`, which is the delta to the FRR for the
+    /// `FRRDELTA*` types. `period` is in days.
+    pub fn new<A, R>(offer_type: FundingOfferType, symbol: FundingCurrency, amount: A, rate: R, period: u32) -> Self
+        where A: fmt::Display, R: fmt::Display
+    {
+        SubmitFundingOffer {
+            offer_type,
+            symbol,
+            amount: amount.to_string(),
+            rate: rate.to_string(),
+            period,
+            flags: None,
+        }
+    }
+
+    /// Adds `flags`, only `OrderFlags::HIDDEN` applies to funding offers.
+    pub fn flag(mut self, flags: OrderFlags) -> Self {
+        self.flags = Some(self.flags.unwrap_or_default() | flags);
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.period < MIN_PERIOD || self.period > MAX_PERIOD {
+            return Err(Error::InvalidRequest(format!(
+                "funding period must be between {} and {} days, got {}", MIN_PERIOD, MAX_PERIOD, self.period
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct CancelFundingOffer {
+    pub(crate) id: i64,
+}
+
+#[derive(Serialize)]
+pub(crate) struct CancelAllFundingOffers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) currency: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct FundingHistoryParams {
+    /// Filter start (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<i64>,
+
+    /// Filter end (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<i64>,
+
+    /// Number of records requested (Max: 500)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+}
+
+impl FundingHistoryParams {
+    pub fn new() -> Self {
+        Self {
+            start: None,
+            end: None,
+            limit: Some(25),
+        }
+    }
+}
+
+/// Funding offers, loans, credits and funding trades.
+#[derive(Clone)]
+pub struct Funding {
+    client: Client,
+}
+
+impl Funding {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Funding::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        Funding {
+            client: Client::with_config(api_key, secret_key, config),
+        }
+    }
+
+    /// Active offers of a currency, or of every currency with `None`.
+    pub fn offers<C>(&self, currency: C) -> Result<Vec<FundingOffer>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("offers", currency.into(), false), "{}".to_string())
+    }
+
+    pub fn offer_history<C>(&self, currency: C, params: &FundingHistoryParams) -> Result<Vec<FundingOffer>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("offers", currency.into(), true), serde_json::to_string(params)?)
+    }
+
+    pub fn submit_offer(&self, req: SubmitFundingOffer) -> Result<FundingOfferNotification> {
+        req.validate()?;
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("funding/offer/submit", payload)
+    }
+
+    pub fn cancel_offer(&self, id: i64) -> Result<FundingOfferNotification> {
+        let payload: String = serde_json::to_string(&CancelFundingOffer { id })?;
+        self.write("funding/offer/cancel", payload)
+    }
+
+    /// Cancels the offers of a currency, or every offer with `None`.
+    pub fn cancel_all_offers<C>(&self, currency: C) -> Result<FundingCancelAllNotification>
+        where C: Into<Option<FundingCurrency>>
+    {
+        let currency = currency.into().map(|c| c.currency().to_owned());
+        let payload: String = serde_json::to_string(&CancelAllFundingOffers { currency })?;
+        self.write("funding/offer/cancel/all", payload)
+    }
+
+    /// Funds lent and not used in a position.
+    pub fn loans<C>(&self, currency: C) -> Result<Vec<FundingLoan>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("loans", currency.into(), false), "{}".to_string())
+    }
+
+    pub fn loan_history<C>(&self, currency: C, params: &FundingHistoryParams) -> Result<Vec<FundingLoan>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("loans", currency.into(), true), serde_json::to_string(params)?)
+    }
+
+    /// Funds used in positions.
+    pub fn credits<C>(&self, currency: C) -> Result<Vec<FundingCredit>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("credits", currency.into(), false), "{}".to_string())
+    }
+
+    pub fn credit_history<C>(&self, currency: C, params: &FundingHistoryParams) -> Result<Vec<FundingCredit>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("credits", currency.into(), true), serde_json::to_string(params)?)
+    }
+
+    pub fn trades<C>(&self, currency: C, params: &FundingHistoryParams) -> Result<Vec<FundingTrade>>
+        where C: Into<Option<FundingCurrency>>
+    {
+        self.read(funding_endpoint("trades", currency.into(), true), serde_json::to_string(params)?)
+    }
+
+    fn read<T: DeserializeOwned>(&self, request: String, payload: String) -> Result<T> {
+        let data = self.client.post_signed(request, payload)?;
+
+        decode(data.as_str())
+    }
+
+    fn write<T: DeserializeOwned>(&self, request: &str, payload: String) -> Result<T> {
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request.into(), payload)?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+}
+
+/// `funding/{kind}[/{currency}][/hist]`
+pub(crate) fn funding_endpoint(kind: &str, currency: Option<FundingCurrency>, hist: bool) -> String {
+    let mut endpoint = format!("funding/{}", kind);
+    if let Some(currency) = currency {
+        endpoint.push_str(&format!("/{}", currency));
+    }
+    if hist {
+        endpoint.push_str("/hist");
+    }
+    endpoint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::from_str;
+
+    #[test]
+    fn test_funding_offer() {
+        let data = "[1574106154011,\"fon-req\",null,null,[41238905,\"fUSD\",1574106154011,1574106154011,1000,1000,\"LIMIT\",null,null,0,\"ACTIVE\",null,null,null,0.002,2,false,0,null,0,null],null,\"SUCCESS\",\"Submitting funding bid of 1000.0 USD at 0.2000 for 2 days.\"]";
+        let offer: FundingOfferNotification = from_str(data).expect("parsed");
+        assert!(!offer.data.notify);
+
+        let data = "[[41238905,\"fUSD\",1574106154011,1574106154011,1000,1000,\"FRRDELTAVAR\",null,null,64,\"ACTIVE\",null,null,null,0,2,0,1,null,0,null]]";
+        let offers: Vec<FundingOffer> = from_str(data).expect("parsed");
+        assert_eq!(offers[0].offer_type, FundingOfferType::FrrDeltaVar);
+        assert!(offers[0].flags.unwrap().contains(OrderFlags::HIDDEN));
+        assert!(offers[0].hidden);
+        assert_eq!(offers[0].symbol, FundingCurrency::new("USD"));
+    }
+
+    #[test]
+    fn test_funding_credit() {
+        let data = "[[26222883,\"fUSD\",1,1574013661000,1574079687000,50,0,\"ACTIVE\",\"FIXED\",null,null,0.0024,2,1574013661000,1574078487000,0,0,null,0,null,0,\"tBTCUSD\"]]";
+        let credits: Vec<FundingCredit> = from_str(data).expect("parsed");
+        assert_eq!(credits[0].rate_type, Some(FundingRateType::Fixed));
+        assert_eq!(credits[0].position_pair.as_ref().map(TradingPair::to_string).as_deref(), Some("tBTCUSD"));
+
+        let loan = "[[2993678,\"fUSD\",-1,1574013661000,1574079687000,200,0,\"ACTIVE\",\"VAR\",null,null,0.002,7,1574013661000,1574078487000,0,0,null,0,null,0]]";
+        let loans: Vec<FundingLoan> = from_str(loan).expect("parsed");
+        assert!(loans[0].position_pair.is_none());
+    }
+
+    #[test]
+    fn test_funding_requests() {
+        let offer = SubmitFundingOffer::new(FundingOfferType::Limit, FundingCurrency::new("USD"), 1000, "0.0002", 2);
+        assert_eq!(
+            serde_json::to_string(&offer).unwrap(),
+            "{\"type\":\"LIMIT\",\"symbol\":\"fUSD\",\"amount\":\"1000\",\"rate\":\"0.0002\",\"period\":2}"
+        );
+        assert!(SubmitFundingOffer::new(FundingOfferType::Limit, FundingCurrency::new("USD"), 1000, 0, 180).validate().is_err());
+
+        assert_eq!(funding_endpoint("offers", None, false), "funding/offers");
+        assert_eq!(funding_endpoint("trades", Some(FundingCurrency::new("UST")), true), "funding/trades/fUST/hist");
+    }
+}
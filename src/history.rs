@@ -0,0 +1,235 @@
+//! Time based paging shared by the history iterators, such as
+//! `Trades::trading_pair_iter` and `Account::movements_iter`.
+
+use crate::errors::*;
+use std::collections::HashSet;
+
+/// An entry of a history endpoint that can be paged through by time.
+pub trait HistoryEntry {
+    fn id(&self) -> i64;
+    fn mts(&self) -> i64;
+}
+
+/// Time window and page size of a history request.
+pub(crate) trait PageParams: Clone {
+    /// Most entries the endpoint returns for a single request
+    const MAX_LIMIT: i32;
+
+    fn limit(&self) -> i32;
+    fn set_limit(&mut self, limit: i32);
+    fn set_start(&mut self, start: i64);
+    fn set_end(&mut self, end: i64);
+
+    /// Whether entries come oldest first
+    fn ascending(&self) -> bool {
+        false
+    }
+}
+
+/// Paging state behind the history iterators and the async pages.
+///
+/// Each page is requested from the millisecond of the last entry received,
+/// as the page limit may have split the entries sharing that millisecond. The
+/// ids already returned for that millisecond are skipped on the next page.
+///
+/// When a whole page holds nothing but that millisecond, it is requested on its
+/// own with the largest limit the endpoint allows before moving past it.
+#[derive(Debug)]
+pub(crate) struct HistoryPager<P> {
+    params: P,
+    boundary: Option<(i64, HashSet<i64>)>,
+    crowded: Option<i64>,
+    done: bool,
+}
+
+impl<P: PageParams> HistoryPager<P> {
+    pub(crate) fn new(params: P) -> Self {
+        HistoryPager { params, boundary: None, crowded: None, done: false }
+    }
+
+    /// Parameters of the next request, `None` once the range is exhausted.
+    pub(crate) fn next_params(&self) -> Option<P> {
+        if self.done {
+            return None;
+        }
+
+        let mut params = self.params.clone();
+        if let Some(mts) = self.crowded {
+            params.set_start(mts);
+            params.set_end(mts);
+            params.set_limit(P::MAX_LIMIT);
+        }
+
+        Some(params)
+    }
+
+    pub(crate) fn finish(&mut self) {
+        self.done = true;
+    }
+
+    /// Drops the entries already returned and moves the window past `page`.
+    pub(crate) fn advance<T: HistoryEntry>(&mut self, page: Vec<T>) -> Result<Vec<T>> {
+        if let Some(mts) = self.crowded.take() {
+            return self.advance_crowded(mts, page);
+        }
+
+        let full = page.len() >= self.params.limit() as usize;
+        let fresh = self.unseen(page);
+
+        let mts = match fresh.last() {
+            Some(last) => last.mts(),
+            None => {
+                // A full page of entries already returned: a single millisecond holds
+                // more entries than the limit, ask for all of it at once.
+                match self.boundary {
+                    Some((mts, _)) if full => self.crowded = Some(mts),
+                    _ => self.done = true,
+                }
+                return Ok(fresh);
+            }
+        };
+
+        let at_boundary = fresh.iter().filter(|entry| entry.mts() == mts).map(HistoryEntry::id);
+        match self.boundary {
+            Some((boundary, ref mut seen)) if boundary == mts => seen.extend(at_boundary),
+            _ => self.boundary = Some((mts, at_boundary.collect())),
+        }
+
+        self.move_window(mts);
+        self.done = !full;
+
+        Ok(fresh)
+    }
+
+    /// Handles the page holding every entry of the crowded millisecond `mts`.
+    fn advance_crowded<T: HistoryEntry>(&mut self, mts: i64, page: Vec<T>) -> Result<Vec<T>> {
+        if page.len() >= P::MAX_LIMIT as usize {
+            self.done = true;
+            return Err(Error::Other(format!(
+                "More than {} history entries at {} ms, they can't be paged through",
+                P::MAX_LIMIT,
+                mts
+            )));
+        }
+
+        let fresh = self.unseen(page);
+        self.boundary = None;
+        self.move_window(if self.params.ascending() { mts + 1 } else { mts - 1 });
+
+        Ok(fresh)
+    }
+
+    fn unseen<T: HistoryEntry>(&self, page: Vec<T>) -> Vec<T> {
+        match self.boundary {
+            Some((_, ref seen)) => page.into_iter().filter(|entry| !seen.contains(&entry.id())).collect(),
+            None => page,
+        }
+    }
+
+    fn move_window(&mut self, mts: i64) {
+        if self.params.ascending() {
+            self.params.set_start(mts);
+        } else {
+            self.params.set_end(mts);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, Default)]
+    struct Params {
+        limit: i32,
+        start: Option<i64>,
+        end: Option<i64>,
+        ascending: bool,
+    }
+
+    impl PageParams for Params {
+        const MAX_LIMIT: i32 = 4;
+
+        fn limit(&self) -> i32 {
+            self.limit
+        }
+
+        fn set_limit(&mut self, limit: i32) {
+            self.limit = limit;
+        }
+
+        fn set_start(&mut self, start: i64) {
+            self.start = Some(start);
+        }
+
+        fn set_end(&mut self, end: i64) {
+            self.end = Some(end);
+        }
+
+        fn ascending(&self) -> bool {
+            self.ascending
+        }
+    }
+
+    struct Entry(i64, i64);
+
+    impl HistoryEntry for Entry {
+        fn id(&self) -> i64 {
+            self.0
+        }
+
+        fn mts(&self) -> i64 {
+            self.1
+        }
+    }
+
+    fn page(entries: &[(i64, i64)]) -> Vec<Entry> {
+        entries.iter().map(|&(id, mts)| Entry(id, mts)).collect()
+    }
+
+    fn ids(entries: Result<Vec<Entry>>) -> Vec<i64> {
+        entries.unwrap().iter().map(|entry| entry.0).collect()
+    }
+
+    #[test]
+    fn test_pager_skips_boundary_duplicates() {
+        let mut pager = HistoryPager::new(Params { limit: 3, end: Some(100), ..Params::default() });
+
+        assert_eq!(ids(pager.advance(page(&[(6, 99), (5, 98), (4, 97)]))), [6, 5, 4]);
+        assert_eq!(pager.next_params().unwrap().end, Some(97));
+
+        // The next page starts again at 97, where entry 3 had been cut off
+        assert_eq!(ids(pager.advance(page(&[(4, 97), (3, 97), (2, 96)]))), [3, 2]);
+        assert_eq!(ids(pager.advance(page(&[(2, 96), (1, 95)]))), [1]);
+        assert!(pager.next_params().is_none());
+    }
+
+    #[test]
+    fn test_pager_requests_a_crowded_millisecond_alone() {
+        let mut pager = HistoryPager::new(Params { limit: 2, start: Some(10), ascending: true, ..Params::default() });
+
+        assert_eq!(ids(pager.advance(page(&[(1, 10), (2, 10)]))), [1, 2]);
+        assert!(ids(pager.advance(page(&[(1, 10), (2, 10)]))).is_empty());
+
+        let params = pager.next_params().unwrap();
+        assert_eq!((params.start, params.end, params.limit), (Some(10), Some(10), 4));
+
+        // Every entry of the millisecond, then on past it with the usual limit
+        assert_eq!(ids(pager.advance(page(&[(1, 10), (2, 10), (3, 10)]))), [3]);
+        let params = pager.next_params().unwrap();
+        assert_eq!((params.start, params.end, params.limit), (Some(11), None, 2));
+
+        assert_eq!(ids(pager.advance(page(&[(4, 12)]))), [4]);
+        assert!(pager.next_params().is_none());
+    }
+
+    #[test]
+    fn test_pager_fails_on_a_millisecond_over_the_max_limit() {
+        let mut pager = HistoryPager::new(Params { limit: 2, end: Some(10), ..Params::default() });
+
+        pager.advance(page(&[(1, 10), (2, 10)])).unwrap();
+        pager.advance(page(&[(1, 10), (2, 10)])).unwrap();
+        assert!(pager.advance(page(&[(1, 10), (2, 10), (3, 10), (4, 10)])).is_err());
+        assert!(pager.next_params().is_none());
+    }
+}
@@ -1,6 +1,6 @@
-use client::*;
-use errors::*;
-use serde_json::from_str;
+use crate::client::*;
+use crate::errors::*;
+use crate::number::Number;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Entry {
@@ -9,8 +9,8 @@ pub struct Entry {
     _field3: Option<()>,
     pub timestamp_milli: i64,
     _field5: Option<()>,
-    pub amount: f64,
-    pub balance: f64,
+    pub amount: Number,
+    pub balance: Number,
     _field8: Option<()>,
     pub description: String,
 }
@@ -21,7 +21,7 @@ pub struct Ledger {
 }
 
 #[derive(Serialize)]
-struct HistoryParams {
+pub(crate) struct HistoryParams {
     pub start: String,
     pub end: String,
     pub limit: i32,
@@ -29,8 +29,12 @@ struct HistoryParams {
 
 impl Ledger {
     pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Ledger::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
         Ledger {
-            client: Client::new(api_key, secret_key),
+            client: Client::with_config(api_key, secret_key, config),
         }
     }
 
@@ -44,17 +48,17 @@ impl Ledger {
     where
         S: Into<String>,
     {
-        let payload: String = format!("{}", "{}");
+        let payload: String = "{}".to_string();
         let request: String = format!("ledgers/{}/hist", symbol.into());
         let params = HistoryParams{
             start: format!("{}", start),
             end: format!("{}", end),
-            limit: limit,
+            limit,
         };
 
         let data = self.client.post_signed_params(request, payload, &params)?;
 
-        let entry: Vec<Entry> = from_str(data.as_str())?;
+        let entry: Vec<Entry> = decode(data.as_str())?;
 
         Ok(entry)
     }
@@ -5,8 +5,6 @@
     unused_imports,
     unused_import_braces)]
 
-#[macro_use] 
-extern crate error_chain;
 extern crate log;
 extern crate hex;
 extern crate ring;
@@ -20,20 +18,39 @@ extern crate url;
 #[macro_use] 
 extern crate serde_derive;
 
+#[macro_use]
+mod macros;
+
 pub mod book;
 pub mod client;
+pub mod config;
 pub mod ticker;
 pub mod trades;
 pub mod orders;
+pub mod positions;
+pub mod funding;
 pub mod account;
+pub mod bolt11;
 pub mod ledger;
+pub mod history;
 pub mod auth;
 
+pub mod calc;
 pub mod candles;
+pub mod derivatives;
 pub mod api;
 pub mod pairs;
+pub mod symbol;
 pub mod currency;
 pub mod precision;
+pub mod stats;
 pub mod websockets;
 pub mod events;
 pub mod errors;
+pub mod notification;
+pub mod number;
+pub mod retry;
+pub mod rate_limit;
+
+#[cfg(feature = "async")]
+pub mod asynchronous;
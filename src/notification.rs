@@ -0,0 +1,32 @@
+use serde_json::Value;
+
+/// Envelope returned by the `auth/w/` write endpoints:
+/// `[MTS, TYPE, MESSAGE_ID, _PLACEHOLDER, DATA, CODE, STATUS, TEXT]`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Notification<T> {
+    pub mts: i64,
+    pub notification_type: String,
+    pub message_id: Option<i64>,
+    #[serde(skip_serializing)]
+    _placeholder: Option<Value>,
+    pub data: T,
+    pub code: Option<i64>,
+    pub status: NotificationStatus,
+    pub text: Option<String>,
+}
+
+string_enum! {
+    /// `STATUS` field of a notification.
+    pub enum NotificationStatus {
+        Success => "SUCCESS",
+        Error => "ERROR",
+        Failure => "FAILURE",
+        Info => "INFO",
+    }
+}
+
+impl NotificationStatus {
+    pub fn is_success(&self) -> bool {
+        *self == NotificationStatus::Success
+    }
+}
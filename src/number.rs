@@ -0,0 +1,90 @@
+//! Numeric type of prices, amounts, balances and fees.
+//!
+//! Without the `decimal` feature this is `f64`. With it, `rust_decimal::Decimal`,
+//! which deserializes from JSON numbers as well as strings and never picks up
+//! binary rounding on the way in or out. The feature turns on serde_json's
+//! `arbitrary_precision` so that numbers reach `Decimal` digit for digit; cargo
+//! enables it for every crate of the build sharing serde_json.
+
+use serde::Deserialize;
+
+#[cfg(not(feature = "decimal"))]
+pub type Number = f64;
+
+#[cfg(feature = "decimal")]
+pub type Number = rust_decimal::Decimal;
+
+/// Deserializes a `Number` sent either as a JSON number or as a string, like
+/// the order sizes in `conf/pub:info:pair`.
+pub(crate) fn string_or_number<'de, D>(deserializer: D) -> Result<Number, D::Error>
+    where D: serde::Deserializer<'de>
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Number(Number),
+        String(String),
+    }
+
+    match Raw::deserialize(deserializer)? {
+        Raw::Number(number) => Ok(number),
+        Raw::String(value) => value.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// Deserializes a flag sent as `0`/`1` by some endpoints and as a boolean by
+/// others, `null` meaning unset.
+pub(crate) fn flag<'de, D>(deserializer: D) -> Result<bool, D::Error>
+    where D: serde::Deserializer<'de>
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(false),
+        Some(serde_json::Value::Bool(value)) => Ok(value),
+        Some(serde_json::Value::Number(value)) => Ok(value.as_f64() != Some(0.0)),
+        Some(value) => Err(serde::de::Error::custom(format!("expected a flag, got {}", value))),
+    }
+}
+
+#[cfg(all(test, feature = "decimal"))]
+mod tests {
+    use super::*;
+    use crate::candles::Candle;
+    use crate::orders::{OrderType, SubmitOrder};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_decimal_from_numbers_and_strings() {
+        let candle: Candle = serde_json::from_str("[1574698260000,7379.785503,\"7383.8\",7386.8,0.1,0.3]").unwrap();
+        assert_eq!(candle.open, Number::from_str("7379.785503").unwrap());
+        assert_eq!(candle.close, Number::from_str("7383.8").unwrap());
+        assert_eq!(candle.low + candle.volume, Number::from_str("0.4").unwrap());
+    }
+
+    #[test]
+    fn test_decimal_keeps_digits_f64_drops() {
+        // 21 significant digits, f64 would round this to 7379.785503123457
+        let exact = Number::from_str("7379.78550312345678901").unwrap();
+
+        let candle: Candle = crate::errors::decode("[1574698260000,7379.78550312345678901,7383.8,7386.8,7379.7,0.3]").unwrap();
+        assert_eq!(candle.open, exact);
+
+        let event = crate::websockets::parse_event("[1,[1574698260000,7379.78550312345678901,7383.8,7386.8,7379.7,0.3]]").unwrap();
+        match event {
+            Some(crate::events::WsEvent::Data(crate::events::DataEvent::CandlesUpdateEvent(_, candle))) => assert_eq!(candle.open, exact),
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn test_decimal_order_request() {
+        let price = Number::from_str("0.1").unwrap() + Number::from_str("0.2").unwrap();
+        let order = SubmitOrder::builder(OrderType::ExchangeLimit, "tBTCUSD", Number::new(-15, 1))
+            .unwrap()
+            .price(price)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&order).unwrap();
+        assert!(json.contains("\"amount\":\"-1.5\"") && json.contains("\"price\":\"0.3\""));
+    }
+}
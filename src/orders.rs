@@ -1,20 +1,28 @@
-use client::*;
-use errors::*;
-use serde_json::{from_str, Value};
+use crate::client::*;
+use crate::errors::*;
+use crate::number::Number;
+use crate::symbol::TradingPair;
+use crate::notification::{Notification, NotificationStatus};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use serde_json::Value;
 use log::*;
+use std::fmt;
+use std::ops;
 
 #[derive(Serialize, Deserialize)]
 pub struct Order { 
     pub id: i64,   
     pub group_id: Option<i32>,                   
     pub client_id: i64,
-    pub symbol: String,
+    pub symbol: TradingPair,
     pub creation_timestamp: i64,
     pub update_timestamp: i64,
-    pub amount: f64,
-    pub amount_original: f64,
-    pub order_type: String,
-    pub previous_order_type: Option<String>,
+    pub amount: Number,
+    pub amount_original: Number,
+    pub order_type: OrderType,
+    pub previous_order_type: Option<OrderType>,
 
     #[serde(skip_serializing)]
     _placeholder_1: Option<String>,
@@ -22,17 +30,17 @@ pub struct Order {
     _placeholder_2: Option<String>,
     
     pub flags: Option<i32>,                   
-    pub order_status: Option<String>,
+    pub order_status: Option<OrderStatus>,
 
     #[serde(skip_serializing)]
     _placeholder_3: Option<String>,
     #[serde(skip_serializing)]
     _placeholder_4: Option<String>,
 
-    pub price: f64,
-    pub price_avg: f64,
-    pub price_trailing: Option<f64>,
-    pub price_aux_limit: Option<f64>,
+    pub price: Number,
+    pub price_avg: Number,
+    pub price_trailing: Option<Number>,
+    pub price_aux_limit: Option<Number>,
     
     #[serde(skip_serializing)]
     __placeholder_5: Option<String>,
@@ -46,13 +54,397 @@ pub struct Order {
     pub placed_id: Option<i32>                      
 }
 
+string_enum! {
+    /// Order type; types this crate doesn't know yet decode as `Unknown`.
+    pub enum OrderType {
+        Limit => "LIMIT",
+        Market => "MARKET",
+        Stop => "STOP",
+        StopLimit => "STOP LIMIT",
+        TrailingStop => "TRAILING STOP",
+        Fok => "FOK",
+        Ioc => "IOC",
+        ExchangeLimit => "EXCHANGE LIMIT",
+        ExchangeMarket => "EXCHANGE MARKET",
+        ExchangeStop => "EXCHANGE STOP",
+        ExchangeStopLimit => "EXCHANGE STOP LIMIT",
+        ExchangeTrailingStop => "EXCHANGE TRAILING STOP",
+        ExchangeFok => "EXCHANGE FOK",
+        ExchangeIoc => "EXCHANGE IOC",
+    }
+}
+
+impl OrderType {
+    /// Exchange orders trade from the exchange wallet, the others are margin orders.
+    pub fn is_exchange(&self) -> bool {
+        self.as_str().starts_with("EXCHANGE")
+    }
+
+    pub fn is_market(&self) -> bool {
+        matches!(*self, OrderType::Market | OrderType::ExchangeMarket)
+    }
+
+    pub fn is_limit(&self) -> bool {
+        matches!(*self, OrderType::Limit | OrderType::ExchangeLimit)
+    }
+
+    pub fn is_stop_limit(&self) -> bool {
+        matches!(*self, OrderType::StopLimit | OrderType::ExchangeStopLimit)
+    }
+
+    pub fn is_trailing_stop(&self) -> bool {
+        matches!(*self, OrderType::TrailingStop | OrderType::ExchangeTrailingStop)
+    }
+}
+
+/// One fill reported in an order status, `PRICE(AMOUNT)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrderFill {
+    pub price: Number,
+    pub amount: Number,
+}
+
+impl OrderFill {
+    fn parse(fill: &str) -> Option<Self> {
+        let (price, amount) = fill.trim().strip_suffix(')')?.split_once('(')?;
+
+        Some(OrderFill { price: price.trim().parse().ok()?, amount: amount.trim().parse().ok()? })
+    }
+}
+
+/// Name of an order status, without its fills.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OrderState {
+    Active,
+    Executed,
+    PartiallyFilled,
+    Canceled,
+    PostOnlyCanceled,
+    IocCanceled,
+    FillOrKillCanceled,
+    InsufficientMargin,
+    /// A status this crate doesn't know, see `OrderStatus::as_str`
+    Unknown,
+}
+
+/// Order status, parsed from strings such as
+/// `EXECUTED @ 107.6(-0.2): was PARTIALLY FILLED @ 107.6(-0.8)`.
+///
+/// The fills are listed in the order Bitfinex reports them, most recent first.
+/// The string Bitfinex sent is kept as is, and is what `Display` and `Serialize`
+/// give back.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderStatus {
+    state: OrderState,
+    fills: Vec<OrderFill>,
+    raw: String,
+}
+
+impl OrderStatus {
+    pub fn state(&self) -> OrderState {
+        self.state
+    }
+
+    pub fn fills(&self) -> &[OrderFill] {
+        &self.fills
+    }
+
+    /// The status as sent by Bitfinex.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Sum of the filled amounts, negative for sell orders.
+    pub fn filled_amount(&self) -> Number {
+        self.fills.iter().map(|fill| fill.amount).sum()
+    }
+
+    /// Whether the order is still on the book.
+    pub fn is_open(&self) -> bool {
+        matches!(self.state, OrderState::Active | OrderState::PartiallyFilled)
+    }
+
+    fn parse(status: &str) -> Option<(OrderState, Vec<OrderFill>)> {
+        let (current, history) = match status.split_once(" was") {
+            Some((current, history)) => (current.trim_end_matches(':'), history.trim_start_matches(':')),
+            None => (status, ""),
+        };
+
+        let (name, mut fills) = match current.split_once(" @ ") {
+            Some((name, fill)) => (name.trim(), vec![OrderFill::parse(fill)?]),
+            None => (current.trim(), Vec::new()),
+        };
+
+        for previous in history.split(',').map(str::trim).filter(|previous| !previous.is_empty()) {
+            let (_, fill) = previous.split_once(" @ ")?;
+            fills.push(OrderFill::parse(fill)?);
+        }
+
+        let state = match name {
+            "ACTIVE" if fills.is_empty() => OrderState::Active,
+            "EXECUTED" => OrderState::Executed,
+            "PARTIALLY FILLED" => OrderState::PartiallyFilled,
+            "CANCELED" => OrderState::Canceled,
+            "POSTONLY CANCELED" => OrderState::PostOnlyCanceled,
+            "IOC CANCELED" => OrderState::IocCanceled,
+            "FILLORKILL CANCELED" => OrderState::FillOrKillCanceled,
+            "INSUFFICIENT MARGIN" => OrderState::InsufficientMargin,
+            _ => return None,
+        };
+
+        Some((state, fills))
+    }
+}
+
+impl From<&str> for OrderStatus {
+    fn from(status: &str) -> Self {
+        let (state, fills) = OrderStatus::parse(status).unwrap_or((OrderState::Unknown, Vec::new()));
+        OrderStatus { state, fills, raw: status.to_owned() }
+    }
+}
+
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl Serialize for OrderStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let status = <Cow<str>>::deserialize(deserializer)?;
+        Ok(OrderStatus::from(status.as_ref()))
+    }
+}
+
+/// Bit flags accepted by `order/submit`, combine them with `|`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OrderFlags(u32);
+
+impl OrderFlags {
+    pub const HIDDEN: OrderFlags = OrderFlags(64);
+    pub const CLOSE: OrderFlags = OrderFlags(512);
+    pub const REDUCE_ONLY: OrderFlags = OrderFlags(1024);
+    pub const POST_ONLY: OrderFlags = OrderFlags(4096);
+    pub const OCO: OrderFlags = OrderFlags(16384);
+    pub const NO_VAR_RATES: OrderFlags = OrderFlags(524288);
+
+    pub fn empty() -> Self {
+        OrderFlags(0)
+    }
+
+    pub fn from_bits(bits: u32) -> Self {
+        OrderFlags(bits)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, other: OrderFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl ops::BitOr for OrderFlags {
+    type Output = OrderFlags;
+
+    fn bitor(self, rhs: OrderFlags) -> OrderFlags {
+        OrderFlags(self.0 | rhs.0)
+    }
+}
+
+impl ops::BitOrAssign for OrderFlags {
+    fn bitor_assign(&mut self, rhs: OrderFlags) {
+        self.0 |= rhs.0;
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SubmitOrder {
     #[serde(rename="type")]
-    pub order_type: String,
-    pub symbol: String,
-    pub amount: String, 
-    pub price: String
+    pub order_type: OrderType,
+    pub symbol: TradingPair,
+    pub amount: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+
+    /// Leverage for derivative orders (1 to 100)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lev: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_trailing: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_aux_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_oco_stop: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gid: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cid: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<OrderFlags>,
+
+    /// Time-In-Force, datetime for automatic cancellation ("2020-01-15 10:45:23")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tif: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+impl SubmitOrder {
+    /// Starts a builder for an order on `pair` (e.g. "tBTCUSD"). A positive
+    /// amount buys, a negative amount sells. Fails if `pair` isn't a trading pair.
+    ///
+    /// Prices and amounts take strings or any number, a `Decimal` with the
+    /// `decimal` feature, and are sent as their exact decimal representation.
+    pub fn builder<P, A>(order_type: OrderType, pair: P, amount: A) -> Result<SubmitOrderBuilder>
+        where P: TryInto<TradingPair>, Error: From<P::Error>, A: fmt::Display
+    {
+        Ok(SubmitOrderBuilder {
+            order: SubmitOrder {
+                order_type,
+                symbol: pair.try_into()?,
+                amount: amount.to_string(),
+                price: None,
+                lev: None,
+                price_trailing: None,
+                price_aux_limit: None,
+                price_oco_stop: None,
+                gid: None,
+                cid: None,
+                flags: None,
+                tif: None,
+                meta: None,
+            }
+        })
+    }
+
+    /// Checks that the fields and flags set are consistent with the order type.
+    pub fn validate(&self) -> Result<()> {
+        let flags = self.flags.unwrap_or_default();
+        let order_type = &self.order_type;
+
+        match self.amount.trim().parse::<Number>() {
+            Ok(amount) if amount != Number::default() => {},
+            _ => return invalid(format!("amount must be a non-zero number, got '{}'", self.amount)),
+        }
+
+        if order_type.is_market() {
+            if self.price.is_some() {
+                return invalid(format!("{} orders don't take a price", order_type));
+            }
+        } else if self.price.is_none() {
+            return invalid(format!("{} orders require a price", order_type));
+        }
+
+        if order_type.is_stop_limit() != self.price_aux_limit.is_some() {
+            return invalid("price_aux_limit is required by, and only valid for, STOP LIMIT orders".into());
+        }
+
+        if order_type.is_trailing_stop() != self.price_trailing.is_some() {
+            return invalid("price_trailing is required by, and only valid for, TRAILING STOP orders".into());
+        }
+
+        if flags.contains(OrderFlags::OCO) != self.price_oco_stop.is_some() {
+            return invalid("price_oco_stop is required by, and only valid with, the OCO flag".into());
+        }
+
+        if flags.contains(OrderFlags::POST_ONLY) && !order_type.is_limit() {
+            return invalid(format!("the post-only flag is not valid for {} orders", order_type));
+        }
+
+        if order_type.is_exchange() && (flags.contains(OrderFlags::REDUCE_ONLY) || flags.contains(OrderFlags::CLOSE)) {
+            return invalid(format!("reduce-only and close flags are not valid for {} orders", order_type));
+        }
+
+        if let Some(lev) = self.lev {
+            if order_type.is_exchange() || !(1..=100).contains(&lev) {
+                return invalid(format!("leverage {} is not valid for a {} order", lev, order_type));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn invalid(message: String) -> Result<()> {
+    Err(Error::InvalidRequest(message))
+}
+
+/// Builder for `SubmitOrder`, `build` validates the order before it can be sent.
+#[derive(Clone, Debug)]
+pub struct SubmitOrderBuilder {
+    order: SubmitOrder,
+}
+
+impl SubmitOrderBuilder {
+    pub fn price<N: fmt::Display>(mut self, price: N) -> Self {
+        self.order.price = Some(price.to_string());
+        self
+    }
+
+    pub fn lev(mut self, lev: u32) -> Self {
+        self.order.lev = Some(lev);
+        self
+    }
+
+    pub fn price_trailing<N: fmt::Display>(mut self, price_trailing: N) -> Self {
+        self.order.price_trailing = Some(price_trailing.to_string());
+        self
+    }
+
+    pub fn price_aux_limit<N: fmt::Display>(mut self, price_aux_limit: N) -> Self {
+        self.order.price_aux_limit = Some(price_aux_limit.to_string());
+        self
+    }
+
+    /// Sets the stop price of an OCO order and adds the OCO flag.
+    pub fn price_oco_stop<N: fmt::Display>(mut self, price_oco_stop: N) -> Self {
+        self.order.price_oco_stop = Some(price_oco_stop.to_string());
+        self.flag(OrderFlags::OCO)
+    }
+
+    pub fn gid(mut self, gid: i64) -> Self {
+        self.order.gid = Some(gid);
+        self
+    }
+
+    pub fn cid(mut self, cid: i64) -> Self {
+        self.order.cid = Some(cid);
+        self
+    }
+
+    /// Adds `flags` to the flags already set.
+    pub fn flag(mut self, flags: OrderFlags) -> Self {
+        self.order.flags = Some(self.order.flags.unwrap_or_default() | flags);
+        self
+    }
+
+    pub fn tif<S: Into<String>>(mut self, tif: S) -> Self {
+        self.order.tif = Some(tif.into());
+        self
+    }
+
+    pub fn meta(mut self, meta: Value) -> Self {
+        self.order.meta = Some(meta);
+        self
+    }
+
+    pub fn build(self) -> Result<SubmitOrder> {
+        self.order.validate()?;
+        Ok(self.order)
+    }
 }
 
 
@@ -65,7 +457,7 @@ pub struct SubmitOrderResp {
     pub _placeholder1: Option<String>,
     pub data: SubmitOrderRespDataWrap,
     pub code: Option<String>, 
-    pub status: String,
+    pub status: NotificationStatus,
     pub text: Option<String>,
 }
 
@@ -79,26 +471,26 @@ pub struct SubmitOrderRespData {
     pub id: i64, 
     pub gid: Option<i64>,
     pub cid: Option<i64>,
-    pub symbol: Option<String>,
+    pub symbol: Option<TradingPair>,
     pub mts_create: i64,
     pub mts_update: i64,
-    pub amount: f64, 
-    pub amount_orig: f64,
-    pub order_type: String, 
-    pub type_prev: Option<String>,
+    pub amount: Number, 
+    pub amount_orig: Number,
+    pub order_type: OrderType,
+    pub type_prev: Option<OrderType>,
     pub mts_tif: Option<i64>,
     #[serde(skip_serializing)]
     pub _placeholder1: Option<String>,
     pub flags: i64, 
-    pub status: String, 
+    pub status: OrderStatus,
     #[serde(skip_serializing)]
     pub _placeholder2: Option<String>,
     #[serde(skip_serializing)]
     pub _placeholder3: Option<String>,
-    pub price: f64, 
-    pub price_avg: f64, 
-    pub price_trailing: f64, 
-    pub price_aux_limit: f64, 
+    pub price: Number, 
+    pub price_avg: Number, 
+    pub price_trailing: Number, 
+    pub price_aux_limit: Number, 
 
     #[serde(skip_serializing)]
     pub _placeholder4: Option<String>,
@@ -126,6 +518,223 @@ pub struct SubmitOrderRespData {
     pub meta: Option<Value>,
 }
   
+/// Notification for a single order, returned by `cancel` and `update`.
+pub type OrderNotification = Notification<SubmitOrderRespData>;
+
+/// Notification listing the affected orders, returned by `cancel_multi` and `cancel_all`.
+pub type OrdersNotification = Notification<Vec<SubmitOrderRespData>>;
+
+/// Notification wrapping one notification per operation, returned by `multi`.
+pub type OrderMultiResp = Notification<Vec<Notification<Option<OrderOpData>>>>;
+
+/// Payload of a notification inside an `order/multi` answer: submits and multi
+/// cancels carry a list of orders, cancels and updates a single one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OrderOpData {
+    Orders(Vec<SubmitOrderRespData>),
+    Order(Box<SubmitOrderRespData>),
+}
+
+/// Identifies the order to cancel, either by id or by client id and the
+/// UTC date ("YYYY-MM-DD") the client id was assigned on.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CancelOrder {
+    Id { id: i64 },
+    Cid { cid: i64, cid_date: String },
+}
+
+impl CancelOrder {
+    pub fn id(id: i64) -> Self {
+        CancelOrder::Id { id }
+    }
+
+    pub fn cid<S: Into<String>>(cid: i64, cid_date: S) -> Self {
+        CancelOrder::Cid { cid, cid_date: cid_date.into() }
+    }
+}
+
+/// Selects several orders to cancel at once.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CancelOrders {
+    #[serde(rename = "id", skip_serializing_if = "Vec::is_empty", default)]
+    pub ids: Vec<i64>,
+    #[serde(rename = "cid", skip_serializing_if = "Vec::is_empty", default)]
+    pub cids: Vec<(i64, String)>,
+    #[serde(rename = "gid", skip_serializing_if = "Vec::is_empty", default)]
+    pub gids: Vec<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all: Option<u8>,
+}
+
+impl CancelOrders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every open order of the account.
+    pub fn all() -> Self {
+        CancelOrders { all: Some(1), ..Self::default() }
+    }
+
+    pub fn id(mut self, id: i64) -> Self {
+        self.ids.push(id);
+        self
+    }
+
+    pub fn cid<S: Into<String>>(mut self, cid: i64, cid_date: S) -> Self {
+        self.cids.push((cid, cid_date.into()));
+        self
+    }
+
+    pub fn gid(mut self, gid: i64) -> Self {
+        self.gids.push(gid);
+        self
+    }
+}
+
+/// Changes to apply to an open order, only the fields set are sent.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UpdateOrder {
+    pub id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+
+    /// Change of amount, as an alternative to setting a new `amount`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delta: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_aux_limit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_trailing: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<OrderFlags>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lev: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gid: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tif: Option<String>,
+}
+
+impl UpdateOrder {
+    pub fn new(id: i64) -> Self {
+        UpdateOrder {
+            id,
+            price: None,
+            amount: None,
+            delta: None,
+            price_aux_limit: None,
+            price_trailing: None,
+            flags: None,
+            lev: None,
+            gid: None,
+            tif: None,
+        }
+    }
+
+    pub fn price<N: fmt::Display>(mut self, price: N) -> Self {
+        self.price = Some(price.to_string());
+        self
+    }
+
+    pub fn amount<N: fmt::Display>(mut self, amount: N) -> Self {
+        self.amount = Some(amount.to_string());
+        self
+    }
+
+    pub fn delta<N: fmt::Display>(mut self, delta: N) -> Self {
+        self.delta = Some(delta.to_string());
+        self
+    }
+
+    pub fn price_aux_limit<N: fmt::Display>(mut self, price_aux_limit: N) -> Self {
+        self.price_aux_limit = Some(price_aux_limit.to_string());
+        self
+    }
+
+    pub fn price_trailing<N: fmt::Display>(mut self, price_trailing: N) -> Self {
+        self.price_trailing = Some(price_trailing.to_string());
+        self
+    }
+
+    /// Replaces the order flags.
+    pub fn flags(mut self, flags: OrderFlags) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn lev(mut self, lev: u32) -> Self {
+        self.lev = Some(lev);
+        self
+    }
+
+    pub fn gid(mut self, gid: i64) -> Self {
+        self.gid = Some(gid);
+        self
+    }
+
+    pub fn tif<S: Into<String>>(mut self, tif: S) -> Self {
+        self.tif = Some(tif.into());
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.amount.is_some() && self.delta.is_some() {
+            return invalid("amount and delta can't be updated together".into());
+        }
+
+        if self.price.is_none() && self.amount.is_none() && self.delta.is_none()
+            && self.price_aux_limit.is_none() && self.price_trailing.is_none()
+            && self.flags.is_none() && self.lev.is_none() && self.gid.is_none() && self.tif.is_none()
+        {
+            return invalid(format!("nothing to update on order {}", self.id));
+        }
+
+        Ok(())
+    }
+}
+
+/// One operation of an `order/multi` batch.
+#[derive(Clone, Debug)]
+pub enum OrderOp {
+    Submit(SubmitOrder),
+    Update(UpdateOrder),
+    Cancel(CancelOrder),
+    CancelMulti(CancelOrders),
+}
+
+impl OrderOp {
+    fn validate(&self) -> Result<()> {
+        match *self {
+            OrderOp::Submit(ref order) => order.validate(),
+            OrderOp::Update(ref update) => update.validate(),
+            OrderOp::Cancel(_) | OrderOp::CancelMulti(_) => Ok(()),
+        }
+    }
+}
+
+impl Serialize for OrderOp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match *self {
+            OrderOp::Submit(ref order) => ("on", order).serialize(serializer),
+            OrderOp::Update(ref update) => ("ou", update).serialize(serializer),
+            OrderOp::Cancel(ref cancel) => ("oc", cancel).serialize(serializer),
+            OrderOp::CancelMulti(ref cancel) => ("oc_multi", cancel).serialize(serializer),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct OrderMultiReq<'a> {
+    pub(crate) ops: &'a [OrderOp],
+}
+
+static MAX_MULTI_OPS: usize = 75;
+
 #[derive(Clone)]
 pub struct Orders {
     client: Client,
@@ -133,28 +742,29 @@ pub struct Orders {
 
 impl Orders {
     pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Orders::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
         Orders {
-            client: Client::new(api_key, secret_key),
+            client: Client::with_config(api_key, secret_key, config),
         }
     }
 
     pub fn active_orders(&self) -> Result<Vec<Order>> {
-        let payload: String = format!("{}", "{}");
+        let payload: String = "{}".to_string();
 
         self.orders("orders".to_owned(), payload)
     }
 
-    pub fn history<T>(&self, symbol: T) -> Result<Vec<Order>>
-        where T: Into<Option<String>>
-    {    
-        let value = symbol.into().unwrap_or("".into());
-        let payload: String = format!("{}", "{}");
+    pub fn history<T>(&self, pair: T) -> Result<Vec<Order>>
+        where T: Into<Option<TradingPair>>
+    {
+        let payload: String = "{}".to_string();
 
-        if value.is_empty() {
-            return self.orders("orders/hist".into(), payload);
-        } else {
-            let request: String = format!("orders/t{}/hist", value);
-            return self.orders(request, payload);
+        match pair.into() {
+            Some(pair) => self.orders(format!("orders/{}/hist", pair), payload),
+            None => self.orders("orders/hist".into(), payload),
         }
     }
 
@@ -163,32 +773,190 @@ impl Orders {
     {    
         let data = self.client.post_signed(request.into(), payload.into())?;
 
-        let orders: Vec<Order> = from_str(data.as_str())?;
+        let orders: Vec<Order> = decode(data.as_str())?;
 
         Ok(orders)
     }
 
     pub fn submit(&self, req: SubmitOrder) -> Result<SubmitOrderResp> {
+        req.validate()?;
         let payload: String = serde_json::to_string(&req)?;
-        let request: String = format!("order/submit");
+        let request: String = "order/submit".to_string();
         debug!("Payload: {payload}");
 
         let data = self.client.post_w_signed(request, payload)?;
         info!("Response: {data}");
 
-        let info: SubmitOrderResp = from_str(data.as_str())?;
+        let info: SubmitOrderResp = decode(data.as_str())?;
 
         Ok(info)
     }
+
+    pub fn cancel(&self, req: CancelOrder) -> Result<OrderNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("order/cancel", payload)
+    }
+
+    pub fn cancel_multi(&self, req: CancelOrders) -> Result<OrdersNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("order/cancel/multi", payload)
+    }
+
+    pub fn cancel_all(&self) -> Result<OrdersNotification> {
+        self.cancel_multi(CancelOrders::all())
+    }
+
+    pub fn update(&self, req: UpdateOrder) -> Result<OrderNotification> {
+        req.validate()?;
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("order/update", payload)
+    }
+
+    /// Sends up to 75 submit, update and cancel operations in one request.
+    pub fn multi(&self, ops: &[OrderOp]) -> Result<OrderMultiResp> {
+        validate_multi(ops)?;
+        let payload: String = serde_json::to_string(&OrderMultiReq { ops })?;
+        self.write("order/multi", payload)
+    }
+
+    fn write<T: DeserializeOwned>(&self, request: &str, payload: String) -> Result<T> {
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request.into(), payload)?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+}
+
+pub(crate) fn validate_multi(ops: &[OrderOp]) -> Result<()> {
+    if ops.is_empty() || ops.len() > MAX_MULTI_OPS {
+        return invalid(format!("order/multi takes 1 to {} operations, got {}", MAX_MULTI_OPS, ops.len()));
+    }
+
+    ops.iter().try_for_each(OrderOp::validate)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::from_str;
 
     #[test]
     fn test_transfer_resp() {
         let data = "[1690988463,\"on-req\",null,null,[[123836039427,null,1690988463421,\"tBTCUST\",1690988463421,1690988463421,-0.00034232,-0.00034232,\"EXCHANGE LIMIT\",null,null,null,0,\"ACTIVE\",null,null,29290,0,0,0,null,null,null,0,0,null,null,null,\"API>BFX\",null,null,{}]],null,\"SUCCESS\",\"Submitting 1 orders.\"]";
         let _: SubmitOrderResp = from_str(data).expect("parsed"); 
     }
+
+    #[test]
+    fn test_submit_order_builder() {
+        let order = SubmitOrder::builder(OrderType::Limit, "tBTCUSD", "0.5")
+            .unwrap()
+            .price("29000")
+            .price_oco_stop("28000")
+            .flag(OrderFlags::POST_ONLY | OrderFlags::HIDDEN)
+            .cid(42)
+            .build()
+            .expect("valid order");
+
+        assert_eq!(
+            serde_json::to_string(&order).unwrap(),
+            "{\"type\":\"LIMIT\",\"symbol\":\"tBTCUSD\",\"amount\":\"0.5\",\"price\":\"29000\",\"price_oco_stop\":\"28000\",\"cid\":42,\"flags\":20544}"
+        );
+    }
+
+    fn fill(price: &str, amount: &str) -> OrderFill {
+        OrderFill { price: price.parse().unwrap(), amount: amount.parse().unwrap() }
+    }
+
+    #[test]
+    fn test_order_status() {
+        assert_eq!(OrderStatus::from("ACTIVE").state(), OrderState::Active);
+        assert_eq!(OrderStatus::from("CANCELED").state(), OrderState::Canceled);
+
+        let status = "EXECUTED @ 107.6(-0.2): was PARTIALLY FILLED @ 107.6(-0.8), PARTIALLY FILLED @ 107.5(-1.5)";
+        let parsed = OrderStatus::from(status);
+        assert_eq!(parsed.state(), OrderState::Executed);
+        assert_eq!(parsed.fills(), &[
+            fill("107.6", "-0.2"),
+            fill("107.6", "-0.8"),
+            fill("107.5", "-1.5"),
+        ]);
+        assert_eq!(parsed.to_string(), status);
+        assert_eq!(parsed.filled_amount(), "-2.5".parse::<Number>().unwrap());
+
+        let status = "INSUFFICIENT MARGIN was: PARTIALLY FILLED @ 8000.0(0.5)";
+        let parsed = OrderStatus::from(status);
+        assert_eq!(parsed.state(), OrderState::InsufficientMargin);
+        assert_eq!(parsed.fills(), &[fill("8000.0", "0.5")]);
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), format!("\"{}\"", status));
+
+        let parsed: OrderStatus = from_str("\"RSN_PAUSE (trading is paused)\"").unwrap();
+        assert_eq!(parsed.state(), OrderState::Unknown);
+        assert_eq!(parsed.as_str(), "RSN_PAUSE (trading is paused)");
+    }
+
+    #[test]
+    fn test_cancel_resp() {
+        let data = "[1568711312683,\"oc-req\",null,null,[31123704044,null,1568711144715,\"tBTCUSD\",1568711144715,1568711144715,0.001,0.001,\"EXCHANGE LIMIT\",null,null,null,0,\"ACTIVE\",null,null,15,0,0,0,null,null,null,0,0,null,null,null,\"API>BFX\",null,null,null],null,\"SUCCESS\",\"Submitted for cancellation; waiting for confirmation (ID: 31123704044).\"]";
+        let resp: OrderNotification = from_str(data).expect("parsed");
+        assert_eq!(resp.data.id, 31123704044);
+    }
+
+    #[test]
+    fn test_unknown_order_type() {
+        let data = "[31123704044,null,1568711144715,\"tBTCUSD\",1568711144715,1568711144715,0.001,0.001,\"EXCHANGE TWAP\",\"EXCHANGE LIMIT\",null,null,0,\"ACTIVE\",null,null,15,0,0,0,null,null,null,0,0,null]";
+        let order: Order = from_str(data).expect("parsed");
+        assert_eq!(order.order_type, OrderType::Unknown("EXCHANGE TWAP".into()));
+        assert_eq!(order.previous_order_type, Some(OrderType::ExchangeLimit));
+        assert_eq!(order.order_type.to_string(), "EXCHANGE TWAP");
+    }
+
+    #[test]
+    fn test_multi_resp() {
+        let order = "[31123704044,null,1568711144715,\"tBTCUSD\",1568711144715,1568711144715,0.001,0.001,\"EXCHANGE LIMIT\",null,null,null,0,\"ACTIVE\",null,null,15,0,0,0,null,null,null,0,0,null,null,null,\"API>BFX\",null,null,null]";
+        let data = format!(
+            "[1611232232,\"ox_multi-req\",null,null,[[1611232232,\"on-req\",null,null,[{}],null,\"SUCCESS\",\"Submitting 1 orders.\"],[1611232232,\"oc-req\",null,null,{},null,\"SUCCESS\",\"Submitted for cancellation\"],[1611232232,\"ou-req\",null,null,null,null,\"ERROR\",\"Order not found.\"]],null,\"SUCCESS\",\"Submitting 3 order operations.\"]",
+            order, order
+        );
+        let resp: OrderMultiResp = from_str(&data).expect("parsed");
+        assert!(matches!(resp.data[0].data, Some(OrderOpData::Orders(_))));
+        assert!(matches!(resp.data[1].data, Some(OrderOpData::Order(_))));
+        assert!(resp.data[2].data.is_none());
+    }
+
+    #[test]
+    fn test_multi_req() {
+        let ops = [
+            OrderOp::Cancel(CancelOrder::id(1)),
+            OrderOp::Cancel(CancelOrder::cid(2, "2024-01-01")),
+            OrderOp::Update(UpdateOrder::new(3).price("10")),
+            OrderOp::CancelMulti(CancelOrders::new().gid(4)),
+        ];
+        assert_eq!(
+            serde_json::to_string(&OrderMultiReq { ops: &ops }).unwrap(),
+            "{\"ops\":[[\"oc\",{\"id\":1}],[\"oc\",{\"cid\":2,\"cid_date\":\"2024-01-01\"}],[\"ou\",{\"id\":3,\"price\":\"10\"}],[\"oc_multi\",{\"gid\":[4]}]]}"
+        );
+        assert!(validate_multi(&[]).is_err());
+        assert!(validate_multi(&[OrderOp::Update(UpdateOrder::new(3))]).is_err());
+    }
+
+    #[test]
+    fn test_submit_order_validation() {
+        assert!(SubmitOrder::builder(OrderType::ExchangeLimit, "tBTCUSD", "1").unwrap().build().is_err());
+        assert!(SubmitOrder::builder(OrderType::Market, "tBTCUSD", "1").unwrap().price("1").build().is_err());
+        assert!(SubmitOrder::builder(OrderType::StopLimit, "tBTCUSD", "1").unwrap().price("1").build().is_err());
+        assert!(SubmitOrder::builder(OrderType::Market, "tBTCUSD", "0").unwrap().build().is_err());
+        assert!(SubmitOrder::builder(OrderType::ExchangeMarket, "tBTCUSD", "1").unwrap().flag(OrderFlags::REDUCE_ONLY).build().is_err());
+        assert!(SubmitOrder::builder(OrderType::Stop, "tBTCUSD", "1").unwrap().price("1").flag(OrderFlags::POST_ONLY).build().is_err());
+        assert!(SubmitOrder::builder(OrderType::Limit, "tBTCUSD", "1").unwrap().price("1").flag(OrderFlags::OCO).build().is_err());
+        assert!(SubmitOrder::builder(OrderType::Limit, "tBTCF0:USTF0", "1").unwrap().price("1").lev(101).build().is_err());
+
+        assert!(SubmitOrder::builder(OrderType::TrailingStop, "tBTCUSD", "-1").unwrap().price("1").price_trailing("10").build().is_ok());
+        assert!(SubmitOrder::builder(OrderType::Limit, "tBTCF0:USTF0", "1").unwrap().price("1").lev(10).build().is_ok());
+
+        assert!(SubmitOrder::builder(OrderType::Market, "fUSD", "1").is_err());
+        let order = SubmitOrder::builder(OrderType::Market, "BTCUSD", "1").unwrap().build().unwrap();
+        assert_eq!(order.symbol.to_string(), "tBTCUSD");
+    }
 }
\ No newline at end of file
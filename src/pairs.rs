@@ -1,40 +1,18 @@
-pub static BTCUSD : &'static str = "BTCUSD";
-pub static LTCUSD : &'static str = "LTCUSD";
-pub static LTCBTC : &'static str = "LTCBTC";
-pub static ETHUSD : &'static str = "ETHUSD";
-pub static ETHBTC : &'static str = "ETHBTC";
-pub static ETCUSD : &'static str = "ETCUSD";
-pub static ETCBTC : &'static str = "ETCBTC";
-pub static BFXUSD : &'static str = "BFXUSD";
-pub static BFXBTC : &'static str = "BFXBTC";
-pub static ZECUSD : &'static str = "ZECUSD";
-pub static ZECBTC : &'static str = "ZECBTC";
-pub static XMRUSD : &'static str = "XMRUSD";   
-pub static XMRBTC : &'static str = "XMRBTC";
-pub static RRTUSD : &'static str = "RRTUSD";
-pub static RRTBTC : &'static str = "RRTBTC"; 
-pub static XRPUSD : &'static str = "XRPUSD";
-pub static XRPBTC : &'static str = "XRPBTC"; 
-pub static EOSETH : &'static str = "EOSETH";
-pub static EOSUSD : &'static str = "EOSUSD";
-pub static EOSBTC : &'static str = "EOSBTC";
-pub static IOTUSD : &'static str = "IOTUSD";
-pub static IOTBTC : &'static str = "IOTBTC";
-pub static IOTETH : &'static str = "IOTETH";
-pub static IOTEUR : &'static str = "IOTEUR";
-pub static BCCBTC : &'static str = "BCCBTC";
-pub static BCUBTC : &'static str = "BCUBTC";
-pub static BCCUSD : &'static str = "BCCUSD";
-pub static BCUUSD : &'static str = "BCUUSD";
-pub static GNTETH : &'static str = "GNTETH";
-pub static GNTUSD : &'static str = "GNTUSD";
-pub static GNTBTC : &'static str = "GNTBTC";
-pub static SANETH : &'static str = "SANETH";
-pub static SANUSD : &'static str = "SANUSD";
-pub static SANBTC : &'static str = "SANBTC";
-pub static AVTETH : &'static str = "AVTETH";
-pub static AVTUSD : &'static str = "AVTUSD";
-pub static AVTBTC : &'static str = "AVTBTC";
-pub static QASHETH : &'static str = "QASHETH";
-pub static QASHUSD : &'static str = "QASHUSD";
-pub static QASHBTC : &'static str = "QASHBTC";
\ No newline at end of file
+use crate::symbol::TradingPair;
+
+pub const BTCUSD: TradingPair = TradingPair::from_static("BTC", "USD");
+pub const BTCEUR: TradingPair = TradingPair::from_static("BTC", "EUR");
+pub const BTCUST: TradingPair = TradingPair::from_static("BTC", "UST");
+pub const ETHUSD: TradingPair = TradingPair::from_static("ETH", "USD");
+pub const ETHBTC: TradingPair = TradingPair::from_static("ETH", "BTC");
+pub const ETHUST: TradingPair = TradingPair::from_static("ETH", "UST");
+pub const LTCUSD: TradingPair = TradingPair::from_static("LTC", "USD");
+pub const LTCBTC: TradingPair = TradingPair::from_static("LTC", "BTC");
+pub const XRPUSD: TradingPair = TradingPair::from_static("XRP", "USD");
+pub const EOSUSD: TradingPair = TradingPair::from_static("EOS", "USD");
+pub const SOLUSD: TradingPair = TradingPair::from_static("SOL", "USD");
+pub const DOGEUSD: TradingPair = TradingPair::from_static("DOGE", "USD");
+pub const USTUSD: TradingPair = TradingPair::from_static("UST", "USD");
+
+/// Paper trading pair
+pub const TESTBTCTESTUSD: TradingPair = TradingPair::from_static("TESTBTC", "TESTUSD");
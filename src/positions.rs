@@ -0,0 +1,306 @@
+use crate::client::*;
+use crate::errors::*;
+use crate::notification::Notification;
+use crate::number::Number;
+use crate::symbol::TradingPair;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use log::*;
+use std::fmt;
+
+string_enum! {
+    pub enum PositionStatus {
+        Active => "ACTIVE",
+        Closed => "CLOSED",
+    }
+}
+
+/// A margin or derivatives position. Profit, liquidation and collateral
+/// fields are only filled in for active positions.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Position {
+    pub symbol: TradingPair,
+    pub status: PositionStatus,
+    /// Size of the position, negative when short
+    pub amount: Number,
+    /// Average price the position was opened at
+    pub base_price: Number,
+    /// Funding accrued by the position
+    pub margin_funding: Option<Number>,
+    /// 0 for daily funding, 1 for term funding
+    pub margin_funding_type: Option<i32>,
+    /// Profit and loss
+    pub pl: Option<Number>,
+    /// Profit and loss in percent
+    pub pl_perc: Option<f64>,
+    /// Liquidation price
+    pub price_liq: Option<Number>,
+    pub leverage: Option<f64>,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    pub position_id: i64,
+    pub mts_create: Option<i64>,
+    pub mts_update: Option<i64>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    /// 0 for margin positions, 1 for derivatives
+    pub position_type: Option<i32>,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    pub collateral: Option<Number>,
+    pub collateral_min: Option<Number>,
+    #[serde(default)]
+    pub meta: Option<Value>,
+}
+
+/// Position affected by `position/increase`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PositionIncrease {
+    pub symbol: TradingPair,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    pub amount: Number,
+    #[serde(skip_serializing, default)]
+    _placeholder_4: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_5: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_6: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_7: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_8: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_9: Option<Value>,
+    #[serde(default)]
+    pub base_price: Option<Number>,
+}
+
+/// Answer of `position/increase/info`: how much a position can grow and the
+/// funding it would take.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PositionIncreaseInfo {
+    pub limits: PositionLimits,
+    pub funding: PositionFunding,
+    #[serde(default)]
+    pub funding_required: Option<PositionFundingRequired>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PositionLimits {
+    pub max_pos: Option<Number>,
+    pub current_pos: Option<Number>,
+    pub base_currency_balance: Option<Number>,
+    pub tradable_balance_quote_currency: Option<Number>,
+    pub tradable_balance_quote_total: Option<Number>,
+    pub tradable_balance_base_currency: Option<Number>,
+    pub tradable_balance_base_total: Option<Number>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PositionFunding {
+    pub funding_avail: Option<Number>,
+    #[serde(skip_serializing, default)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_2: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_3: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_4: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PositionFundingRequired {
+    pub funding_value: Option<Number>,
+    pub funding_required: Option<Number>,
+    #[serde(default)]
+    pub funding_value_currency: Option<String>,
+    #[serde(default)]
+    pub funding_required_currency: Option<String>,
+}
+
+pub type PositionNotification = Notification<Position>;
+pub type PositionIncreaseNotification = Notification<PositionIncrease>;
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct PositionHistoryParams {
+    /// Filter start (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<i64>,
+
+    /// Filter end (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<i64>,
+
+    /// Number of records requested (Max: 500)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
+}
+
+impl PositionHistoryParams {
+    pub fn new() -> Self {
+        Self {
+            start: None,
+            end: None,
+            limit: Some(50),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub(crate) struct PositionAuditReq<'a> {
+    #[serde(rename = "id", skip_serializing_if = "<[i64]>::is_empty")]
+    pub(crate) ids: &'a [i64],
+    #[serde(flatten)]
+    pub(crate) params: &'a PositionHistoryParams,
+}
+
+/// Closes a position, or part of it, using the funds in the margin wallet
+/// instead of trading.
+#[derive(Serialize, Debug, Clone)]
+pub struct ClaimPosition {
+    id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    amount: Option<String>,
+}
+
+impl ClaimPosition {
+    pub fn new(id: i64) -> Self {
+        ClaimPosition { id, amount: None }
+    }
+
+    /// Claims only `amount` of the position.
+    pub fn amount<N: fmt::Display>(mut self, amount: N) -> Self {
+        self.amount = Some(amount.to_string());
+        self
+    }
+}
+
+/// Increases a position without placing an order, used by both
+/// `position/increase` and `position/increase/info`.
+#[derive(Serialize, Debug, Clone)]
+pub struct IncreasePosition {
+    symbol: TradingPair,
+    amount: String,
+}
+
+impl IncreasePosition {
+    pub fn new<N: fmt::Display>(symbol: TradingPair, amount: N) -> Self {
+        IncreasePosition { symbol, amount: amount.to_string() }
+    }
+}
+
+#[derive(Clone)]
+pub struct Positions {
+    client: Client,
+}
+
+impl Positions {
+    pub fn new(api_key: Option<String>, secret_key: Option<String>) -> Self {
+        Positions::with_config(api_key, secret_key, ClientConfig::default())
+    }
+
+    pub fn with_config(api_key: Option<String>, secret_key: Option<String>, config: ClientConfig) -> Self {
+        Positions {
+            client: Client::with_config(api_key, secret_key, config),
+        }
+    }
+
+    pub fn active_positions(&self) -> Result<Vec<Position>> {
+        self.positions("positions", "{}".to_string())
+    }
+
+    pub fn history(&self, params: &PositionHistoryParams) -> Result<Vec<Position>> {
+        self.positions("positions/hist", serde_json::to_string(params)?)
+    }
+
+    /// Positions as they were at the end of each day of the range.
+    pub fn snapshots(&self, params: &PositionHistoryParams) -> Result<Vec<Position>> {
+        self.positions("positions/snap", serde_json::to_string(params)?)
+    }
+
+    /// Every change of the given positions, or of all positions when `ids` is empty.
+    pub fn audit(&self, ids: &[i64], params: &PositionHistoryParams) -> Result<Vec<Position>> {
+        self.positions("positions/audit", serde_json::to_string(&PositionAuditReq { ids, params })?)
+    }
+
+    pub fn claim(&self, req: ClaimPosition) -> Result<PositionNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("position/claim", payload)
+    }
+
+    pub fn increase(&self, req: IncreasePosition) -> Result<PositionIncreaseNotification> {
+        let payload: String = serde_json::to_string(&req)?;
+        self.write("position/increase", payload)
+    }
+
+    pub fn increase_info(&self, req: &IncreasePosition) -> Result<PositionIncreaseInfo> {
+        let payload: String = serde_json::to_string(req)?;
+        let data = self.client.post_signed("position/increase/info".into(), payload)?;
+
+        let info: PositionIncreaseInfo = decode(data.as_str())?;
+
+        Ok(info)
+    }
+
+    fn positions(&self, request: &str, payload: String) -> Result<Vec<Position>> {
+        let data = self.client.post_signed(request.into(), payload)?;
+
+        let positions: Vec<Position> = decode(data.as_str())?;
+
+        Ok(positions)
+    }
+
+    fn write<T: DeserializeOwned>(&self, request: &str, payload: String) -> Result<T> {
+        debug!("Payload: {payload}");
+
+        let data = self.client.post_w_signed(request.into(), payload)?;
+        info!("Response: {data}");
+
+        decode(data.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::from_str;
+    use crate::pairs::BTCUSD;
+
+    #[test]
+    fn test_position() {
+        let data = "[[\"tBTCUSD\",\"ACTIVE\",0.0195,8565.0267019,0,0,-0.33455568705000516,-0.0003117550117425625,7045.876419249083,3.0673001895895604,null,142355652,1574002216000,1574002216000,null,0,null,null,null,{\"reason\":\"TRADE\"}]]";
+        let positions: Vec<Position> = from_str(data).expect("parsed");
+        assert_eq!(positions[0].symbol, BTCUSD);
+        assert_eq!(positions[0].status, PositionStatus::Active);
+        assert_eq!(positions[0].position_id, 142355652);
+        assert!(positions[0].price_liq.is_some());
+
+        let data = "[1575289447641,\"pos_claim\",null,null,[\"tETHUSD\",\"ACTIVE\",-0.2,167.01,0,0,null,null,null,null,null,142031891,null,null,null,0,null,null,null,null],null,\"SUCCESS\",\"Position claimed\"]";
+        let claim: PositionNotification = from_str(data).expect("parsed");
+        assert!(claim.status.is_success());
+        assert!(claim.data.pl.is_none());
+    }
+
+    #[test]
+    fn test_position_requests() {
+        let params = PositionHistoryParams { start: Some(1), ..PositionHistoryParams::new() };
+        let audit = PositionAuditReq { ids: &[142031891], params: &params };
+        assert_eq!(serde_json::to_string(&audit).unwrap(), "{\"id\":[142031891],\"start\":1,\"limit\":50}");
+
+        let claim = ClaimPosition::new(142031891).amount("0.1");
+        assert_eq!(serde_json::to_string(&claim).unwrap(), "{\"id\":142031891,\"amount\":\"0.1\"}");
+
+        let increase = IncreasePosition::new(BTCUSD, 0.001);
+        assert_eq!(serde_json::to_string(&increase).unwrap(), "{\"symbol\":\"tBTCUSD\",\"amount\":\"0.001\"}");
+
+        let data = "[[0.5,0.1,0,1000,1000,0.12,0.12],[500,null,null,null,null],[0,0,\"USD\",\"BTC\"]]";
+        let info: PositionIncreaseInfo = from_str(data).expect("parsed");
+        assert_eq!(info.limits.max_pos, Some("0.5".parse::<Number>().unwrap()));
+    }
+}
@@ -1,5 +1,39 @@
-pub static P0 : &'static str = "P0";
-pub static P1 : &'static str = "P1";
-pub static P2 : &'static str = "P2";
-pub static P3 : &'static str = "P3";
-pub static R0 : &'static str = "R0";
\ No newline at end of file
+use std::fmt;
+
+pub use self::Precision::{P0, P1, P2, P3, P4, R0};
+
+/// Price aggregation level of an order book, `R0` being the raw book.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Precision {
+    P0,
+    P1,
+    P2,
+    P3,
+    P4,
+    R0,
+}
+
+impl Precision {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Precision::P0 => "P0",
+            Precision::P1 => "P1",
+            Precision::P2 => "P2",
+            Precision::P3 => "P3",
+            Precision::P4 => "P4",
+            Precision::R0 => "R0",
+        }
+    }
+}
+
+impl fmt::Display for Precision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<Precision> for String {
+    fn from(precision: Precision) -> Self {
+        precision.as_str().to_owned()
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of requests allowed per period for one endpoint family.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quota {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl Quota {
+    pub fn new(requests: u32, period: Duration) -> Self {
+        Quota { requests, period }
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        Quota::new(requests, Duration::from_secs(60))
+    }
+
+    fn rate(&self) -> f64 {
+        f64::from(self.requests.max(1)) / self.period.as_secs_f64()
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Client-side token bucket limiter, one bucket per endpoint family.
+///
+/// The family of a request is the first path segment of the endpoint, e.g.
+/// `ticker`, `trades` or `candles`, prefixed with `auth/r/` or `auth/w/` for
+/// authenticated calls. Families without an explicit quota use the default one.
+#[derive(Debug)]
+pub struct RateLimiter {
+    default_quota: Quota,
+    quotas: HashMap<String, Quota>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(default_quota: Quota) -> Self {
+        RateLimiter {
+            default_quota,
+            quotas: HashMap::new(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Limits matching the per-minute quotas published in the Bitfinex REST documentation.
+    pub fn bitfinex() -> Self {
+        RateLimiter::new(Quota::per_minute(90))
+            .family("platform", Quota::per_minute(30))
+            .family("tickers", Quota::per_minute(30))
+            .family("trades", Quota::per_minute(15))
+            .family("candles", Quota::per_minute(30))
+            .family("liquidations", Quota::per_minute(3))
+    }
+
+    pub fn family<S: Into<String>>(mut self, family: S, quota: Quota) -> Self {
+        self.quotas.insert(family.into(), quota);
+        self
+    }
+
+    pub fn quota(&self, family: &str) -> Quota {
+        self.quotas.get(family).copied().unwrap_or(self.default_quota)
+    }
+
+    /// Takes a token for `family` and returns how long the caller has to wait
+    /// before sending. Tokens are reserved, so concurrent callers queue up
+    /// instead of all waking at the same time.
+    pub fn reserve(&self, family: &str) -> Duration {
+        let quota = self.quota(family);
+        let capacity = f64::from(quota.requests.max(1));
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets.entry(family.to_owned()).or_insert(Bucket { tokens: capacity, updated: now });
+
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * quota.rate()).min(capacity) - 1.0;
+        bucket.updated = now;
+
+        if bucket.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / quota.rate())
+        }
+    }
+}
+
+/// Endpoint family used as the rate limiter key, see `RateLimiter`.
+pub(crate) fn endpoint_family(prefix: &str, endpoint: &str) -> String {
+    let segment = endpoint.split(['/', '?']).next().unwrap_or_default();
+    format!("{}{}", prefix, segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_family() {
+        assert_eq!(endpoint_family("", "ticker/tBTCUSD"), "ticker");
+        assert_eq!(endpoint_family("auth/w/", "order/submit"), "auth/w/order");
+    }
+
+    #[test]
+    fn test_reserve() {
+        let limiter = RateLimiter::new(Quota::per_minute(90)).family("trades", Quota::per_minute(2));
+
+        assert_eq!(limiter.reserve("trades"), Duration::from_secs(0));
+        assert_eq!(limiter.reserve("trades"), Duration::from_secs(0));
+
+        let wait = limiter.reserve("trades");
+        assert!(wait > Duration::from_secs(29) && wait <= Duration::from_secs(30));
+
+        assert_eq!(limiter.reserve("ticker"), Duration::from_secs(0));
+    }
+}
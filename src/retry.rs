@@ -0,0 +1,161 @@
+use crate::errors::*;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::time::Duration;
+
+pub static DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Controls how `Client` retries failed requests.
+///
+/// Delays grow exponentially from `initial_backoff` up to `max_backoff`. A
+/// `Retry-After` header sent with a rate limit answer takes precedence over
+/// the computed delay. Writes (`auth/w/`) are not retried unless
+/// `retry_writes` is set, since a timed out write may still have been applied.
+///
+/// The default policy retries reads up to `DEFAULT_MAX_RETRIES` times.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt, 0 disables retrying
+    pub max_retries: u32,
+
+    pub initial_backoff: Duration,
+
+    pub max_backoff: Duration,
+
+    /// Randomizes each delay between half and the full backoff
+    pub jitter: bool,
+
+    /// Also retry non-idempotent `auth/w/` requests
+    pub retry_writes: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(60),
+            jitter: true,
+            retry_writes: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self::default().max_retries(0)
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn retry_writes(mut self, retry_writes: bool) -> Self {
+        self.retry_writes = retry_writes;
+        self
+    }
+
+    /// Returns how long to wait before retrying `error`, or `None` if the
+    /// request should fail. `attempt` is the number of retries already made.
+    pub fn retry_delay(&self, error: &Error, attempt: u32, write: bool) -> Option<Duration> {
+        if attempt >= self.max_retries || (write && !self.retry_writes) || !error.is_retryable() {
+            return None;
+        }
+
+        if let Error::RateLimited { retry_after: Some(retry_after) } = *error {
+            return Some(retry_after);
+        }
+
+        Some(self.backoff(attempt))
+    }
+
+    /// Exponential backoff for the given retry attempt, with jitter if enabled.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        let backoff = self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |b| b.min(self.max_backoff));
+
+        if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(random_fraction())
+        } else {
+            backoff
+        }
+    }
+}
+
+fn random_fraction() -> f64 {
+    let mut bytes = [0u8; 4];
+    match SystemRandom::new().fill(&mut bytes) {
+        Ok(()) => f64::from(u32::from_le_bytes(bytes)) / f64::from(u32::MAX),
+        Err(_) => 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new()
+            .max_retries(10)
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(10))
+            .jitter(false);
+
+        assert_eq!(policy.backoff(0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(4));
+        assert_eq!(policy.backoff(5), Duration::from_secs(10));
+        assert_eq!(policy.backoff(64), Duration::from_secs(10));
+
+        let policy = policy.jitter(true);
+        let delay = policy.backoff(2);
+        assert!(delay >= Duration::from_secs(2) && delay <= Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::new().max_retries(2).jitter(false);
+        let limited = Error::RateLimited { retry_after: Some(Duration::from_secs(60)) };
+
+        assert_eq!(policy.retry_delay(&limited, 0, false), Some(Duration::from_secs(60)));
+        assert_eq!(policy.retry_delay(&limited, 2, false), None);
+        assert_eq!(policy.retry_delay(&limited, 0, true), None);
+        assert_eq!(policy.retry_writes(true).retry_delay(&limited, 0, true), Some(Duration::from_secs(60)));
+
+        let api = Error::Api { code: 10020, message: "symbol: invalid".into() };
+        assert_eq!(RetryPolicy::new().max_retries(2).retry_delay(&api, 0, false), None);
+    }
+
+    #[test]
+    fn test_default_retries_reads_only() {
+        let limited = Error::RateLimited { retry_after: None };
+
+        assert!(RetryPolicy::default().retry_delay(&limited, 0, false).is_some());
+        assert!(RetryPolicy::default().retry_delay(&limited, DEFAULT_MAX_RETRIES, false).is_none());
+        assert!(RetryPolicy::default().retry_delay(&limited, 0, true).is_none());
+        assert!(RetryPolicy::none().retry_delay(&limited, 0, false).is_none());
+    }
+}
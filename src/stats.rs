@@ -0,0 +1,308 @@
+use crate::client::*;
+use crate::errors::*;
+use crate::number::Number;
+use crate::symbol::{FundingCurrency, TradingPair};
+use serde_json::Value;
+use std::fmt;
+
+/// Side of the margin positions counted by `StatsKey::PositionSize`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Long,
+    Short,
+}
+
+impl Side {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Side::Long => "long",
+            Side::Short => "short",
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Whether `stats1` returns the latest value or its history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Section {
+    Last,
+    Hist,
+}
+
+impl Section {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Section::Last => "last",
+            Section::Hist => "hist",
+        }
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Window over which the platform volume is summed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VolumePeriod {
+    OneDay,
+    SevenDays,
+    ThirtyDays,
+}
+
+impl VolumePeriod {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            VolumePeriod::OneDay => "1d",
+            VolumePeriod::SevenDays => "7d",
+            VolumePeriod::ThirtyDays => "30d",
+        }
+    }
+}
+
+/// A `stats1` series. Displays as the `{key}:{size}:{symbol}` part of the path,
+/// the size being the only one Bitfinex serves for that key.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StatsKey {
+    /// Total open margin positions on one side of a pair, `pos.size:1m:tBTCUSD:long`
+    PositionSize { pair: TradingPair, side: Side },
+    /// Total active funding, `funding.size:1m:fUSD`
+    FundingSize(FundingCurrency),
+    /// Funding used in positions, `credits.size:1m:fUSD`
+    CreditsSize(FundingCurrency),
+    /// Funding used in positions on a given pair, `credits.size.sym:1m:fUSD:tBTCUSD`
+    CreditsSizeSym(FundingCurrency, TradingPair),
+    /// Trading volume of the whole platform, `vol.1d:30m:BFX`
+    Volume(VolumePeriod),
+    /// Volume weighted average price over a day, `vwap:1d:tBTCUSD`
+    Vwap(TradingPair),
+}
+
+impl fmt::Display for StatsKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StatsKey::PositionSize { ref pair, side } => write!(f, "pos.size:1m:{}:{}", pair, side),
+            StatsKey::FundingSize(ref currency) => write!(f, "funding.size:1m:{}", currency),
+            StatsKey::CreditsSize(ref currency) => write!(f, "credits.size:1m:{}", currency),
+            StatsKey::CreditsSizeSym(ref currency, ref pair) => write!(f, "credits.size.sym:1m:{}:{}", currency, pair),
+            StatsKey::Volume(period) => write!(f, "vol.{}:30m:BFX", period.as_str()),
+            StatsKey::Vwap(ref pair) => write!(f, "vwap:1d:{}", pair),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StatsValue {
+    pub mts: i64,
+    pub value: Number,
+}
+
+/// Leaderboard of `rankings`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RankingKey {
+    /// Unrealized profit change
+    PluDiff,
+    /// Unrealized profit
+    Plu,
+    /// Volume
+    Vol,
+    /// Realized profit
+    Plr,
+}
+
+impl RankingKey {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            RankingKey::PluDiff => "plu_diff",
+            RankingKey::Plu => "plu",
+            RankingKey::Vol => "vol",
+            RankingKey::Plr => "plr",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RankingPeriod {
+    ThreeHours,
+    OneWeek,
+    OneMonth,
+}
+
+impl RankingPeriod {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            RankingPeriod::ThreeHours => "3h",
+            RankingPeriod::OneWeek => "1w",
+            RankingPeriod::OneMonth => "1M",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Ranking {
+    pub mts: i64,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    pub username: String,
+    pub ranking: i64,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    pub value: Number,
+    #[serde(skip_serializing, default)]
+    _placeholder_4: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_5: Option<Value>,
+    #[serde(default)]
+    pub twitter_handle: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatsHistoryParams {
+    /// Number of records requested (Max: 10000)
+    pub limit: Option<i32>,
+
+    /// Filter start (ms)
+    pub start: Option<i64>,
+
+    /// Filter end (ms)
+    pub end: Option<i64>,
+
+    /// Sorts the results from old > new
+    pub sort: Option<bool>,
+}
+
+impl StatsHistoryParams {
+    pub fn new() -> Self {
+        Self {
+            limit: Some(100),
+            sort: Some(false),
+            start: None,
+            end: None,
+        }
+    }
+
+    pub fn to_query(&self) -> String {
+        format!("{}={}&{}={}&{}={}&{}={}",
+            "limit", self.limit
+                         .map(|a| a.to_string())
+                         .unwrap_or_default(),
+            "start", self.start
+                         .map(|a| a.to_string())
+                         .unwrap_or_default(),
+            "end", self.end
+                       .map(|a| a.to_string())
+                       .unwrap_or_default(),
+            "sort", self.sort
+                        .map(|a| if a { "1" } else { "-1" })
+                        .unwrap_or(""),
+        )
+    }
+}
+
+/// Market statistics (`stats1`) and leaderboards (`rankings`).
+#[derive(Clone)]
+pub struct Stats {
+    client: Client,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
+        Stats {
+            client: Client::with_config(None, None, config),
+        }
+    }
+
+    pub fn last(&self, key: &StatsKey) -> Result<StatsValue> {
+        let endpoint = stats_endpoint(key, Section::Last);
+        let data = self.client.get(endpoint, String::new())?;
+
+        let value: StatsValue = decode(data.as_str())?;
+
+        Ok(value)
+    }
+
+    pub fn history(&self, key: &StatsKey, params: &StatsHistoryParams) -> Result<Vec<StatsValue>> {
+        let endpoint = stats_endpoint(key, Section::Hist);
+        let data = self.client.get(endpoint, params.to_query())?;
+
+        let values: Vec<StatsValue> = decode(data.as_str())?;
+
+        Ok(values)
+    }
+
+    /// Leaderboard of a pair, e.g. `tBTCUSD` or `tGLOBAL:USD` across all pairs.
+    pub fn rankings<P>(
+        &self,
+        key: RankingKey,
+        period: RankingPeriod,
+        pair: P,
+        params: &StatsHistoryParams,
+    ) -> Result<Vec<Ranking>>
+        where P: TryInto<TradingPair>, Error: From<P::Error>
+    {
+        let endpoint = rankings_endpoint(key, period, &pair.try_into()?);
+        let data = self.client.get(endpoint, params.to_query())?;
+
+        let rankings: Vec<Ranking> = decode(data.as_str())?;
+
+        Ok(rankings)
+    }
+}
+
+pub(crate) fn stats_endpoint(key: &StatsKey, section: Section) -> String {
+    format!("stats1/{}/{}", key, section)
+}
+
+pub(crate) fn rankings_endpoint(key: RankingKey, period: RankingPeriod, pair: &TradingPair) -> String {
+    format!("rankings/{}:{}:{}/{}", key.as_str(), period.as_str(), pair, Section::Hist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::from_str;
+    use crate::pairs::BTCUSD;
+
+    #[test]
+    fn test_stats_key() {
+        let key = StatsKey::PositionSize { pair: BTCUSD, side: Side::Long };
+        assert_eq!(stats_endpoint(&key, Section::Hist), "stats1/pos.size:1m:tBTCUSD:long/hist");
+
+        let key = StatsKey::CreditsSizeSym(FundingCurrency::new("USD"), BTCUSD);
+        assert_eq!(key.to_string(), "credits.size.sym:1m:fUSD:tBTCUSD");
+        assert_eq!(StatsKey::Volume(VolumePeriod::SevenDays).to_string(), "vol.7d:30m:BFX");
+
+        let pair = TradingPair::new("GLOBAL", "USD");
+        assert_eq!(rankings_endpoint(RankingKey::PluDiff, RankingPeriod::OneWeek, &pair), "rankings/plu_diff:1w:tGLOBAL:USD/hist");
+    }
+
+    #[test]
+    fn test_stats_values() {
+        let values: Vec<StatsValue> = from_str("[[1573554000000,8243.80029531],[1573553940000,8246.16467598]]").expect("parsed");
+        assert_eq!(values[0].mts, 1573554000000);
+
+        let data = "[[1573236000000,null,\"user\",1,null,null,1073210.4,null,null,\"handle\"]]";
+        let rankings: Vec<Ranking> = from_str(data).expect("parsed");
+        assert_eq!(rankings[0].ranking, 1);
+        assert_eq!(rankings[0].twitter_handle.as_deref(), Some("handle"));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::errors::*;
+use serde::de;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// A trading pair, rendered as `tBTCUSD` or, when either side is longer
+/// than three characters, as `tTESTBTC:TESTUSD`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TradingPair {
+    base: Cow<'static, str>,
+    quote: Cow<'static, str>,
+}
+
+impl TradingPair {
+    pub fn new<B, Q>(base: B, quote: Q) -> Self
+        where B: Into<String>, Q: Into<String>
+    {
+        TradingPair { base: Cow::Owned(base.into()), quote: Cow::Owned(quote.into()) }
+    }
+
+    pub const fn from_static(base: &'static str, quote: &'static str) -> Self {
+        TradingPair { base: Cow::Borrowed(base), quote: Cow::Borrowed(quote) }
+    }
+
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    pub fn quote(&self) -> &str {
+        &self.quote
+    }
+
+    /// The pair without the `t` prefix, as used in `conf/pub:list:pair:*` lists.
+    pub fn pair(&self) -> String {
+        if self.base.len() == 3 && self.quote.len() == 3 {
+            format!("{}{}", self.base, self.quote)
+        } else {
+            format!("{}:{}", self.base, self.quote)
+        }
+    }
+}
+
+impl fmt::Display for TradingPair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "t{}", self.pair())
+    }
+}
+
+/// Parses `tBTCUSD`, `tTESTBTC:TESTUSD` or the same without the `t` prefix.
+impl FromStr for TradingPair {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let pair = value.strip_prefix('t').unwrap_or(value);
+
+        let (base, quote) = match pair.split_once(':') {
+            Some(sides) => sides,
+            None if pair.len() == 6 && pair.is_char_boundary(3) => pair.split_at(3),
+            None => return Err(Error::InvalidSymbol(value.to_owned())),
+        };
+
+        if !is_ticker(base) || !is_ticker(quote) {
+            return Err(Error::InvalidSymbol(value.to_owned()));
+        }
+
+        Ok(TradingPair::new(base, quote))
+    }
+}
+
+/// A funding currency, rendered as `fUSD`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FundingCurrency {
+    currency: Cow<'static, str>,
+}
+
+impl FundingCurrency {
+    pub fn new<S: Into<String>>(currency: S) -> Self {
+        FundingCurrency { currency: Cow::Owned(currency.into()) }
+    }
+
+    pub const fn from_static(currency: &'static str) -> Self {
+        FundingCurrency { currency: Cow::Borrowed(currency) }
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+}
+
+impl fmt::Display for FundingCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "f{}", self.currency)
+    }
+}
+
+/// Parses `fUSD` or the bare currency `USD`.
+impl FromStr for FundingCurrency {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let currency = value.strip_prefix('f').unwrap_or(value);
+
+        if !is_ticker(currency) {
+            return Err(Error::InvalidSymbol(value.to_owned()));
+        }
+
+        Ok(FundingCurrency::new(currency))
+    }
+}
+
+/// Either kind of symbol, told apart by the `t` or `f` prefix.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Symbol {
+    Trading(TradingPair),
+    Funding(FundingCurrency),
+}
+
+impl Symbol {
+    pub fn is_trading(&self) -> bool {
+        matches!(*self, Symbol::Trading(_))
+    }
+
+    pub fn is_funding(&self) -> bool {
+        matches!(*self, Symbol::Funding(_))
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Symbol::Trading(ref pair) => pair.fmt(f),
+            Symbol::Funding(ref currency) => currency.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        if value.starts_with('t') {
+            value.parse().map(Symbol::Trading)
+        } else if value.starts_with('f') {
+            value.parse().map(Symbol::Funding)
+        } else {
+            Err(Error::InvalidSymbol(value.to_owned()))
+        }
+    }
+}
+
+impl From<TradingPair> for Symbol {
+    fn from(pair: TradingPair) -> Self {
+        Symbol::Trading(pair)
+    }
+}
+
+impl From<FundingCurrency> for Symbol {
+    fn from(currency: FundingCurrency) -> Self {
+        Symbol::Funding(currency)
+    }
+}
+
+/// Currencies and pair sides are upper case alphanumerics, which is what tells
+/// the `t`/`f` prefix apart from the symbol itself.
+pub(crate) fn is_ticker(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+macro_rules! symbol_conversions {
+    ($name:ident) => {
+        impl TryFrom<&str> for $name {
+            type Error = Error;
+
+            fn try_from(value: &str) -> Result<Self> {
+                value.parse()
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+
+            fn try_from(value: String) -> Result<Self> {
+                value.parse()
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(value: &$name) -> Self {
+                value.clone()
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let value = <Cow<str>>::deserialize(deserializer)?;
+                value.parse().map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+symbol_conversions!(TradingPair);
+symbol_conversions!(FundingCurrency);
+symbol_conversions!(Symbol);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trading_pair() {
+        let pair: TradingPair = "tBTCUSD".parse().unwrap();
+        assert_eq!((pair.base(), pair.quote()), ("BTC", "USD"));
+        assert_eq!(pair.to_string(), "tBTCUSD");
+        assert_eq!("BTCUSD".parse::<TradingPair>().unwrap(), pair);
+
+        let pair: TradingPair = "tTESTBTC:TESTUSD".parse().unwrap();
+        assert_eq!((pair.base(), pair.quote()), ("TESTBTC", "TESTUSD"));
+        assert_eq!(pair.to_string(), "tTESTBTC:TESTUSD");
+        assert_eq!(TradingPair::new("DOGE", "USD").to_string(), "tDOGE:USD");
+
+        assert!("tBTCUS".parse::<TradingPair>().is_err());
+        assert!("fUSD".parse::<TradingPair>().is_err());
+    }
+
+    #[test]
+    fn test_symbol() {
+        assert_eq!("fUSD".parse::<Symbol>().unwrap(), Symbol::Funding(FundingCurrency::new("USD")));
+        assert!("tETHBTC".parse::<Symbol>().unwrap().is_trading());
+        assert!("USD".parse::<Symbol>().is_err());
+
+        let symbols: Vec<Symbol> = serde_json::from_str("[\"tBTCF0:USTF0\",\"fUST\"]").unwrap();
+        assert_eq!(serde_json::to_string(&symbols).unwrap(), "[\"tBTCF0:USTF0\",\"fUST\"]");
+    }
+}
@@ -1,36 +1,119 @@
-use client::*;
-use errors::*;
-use serde_json::from_str;
+use crate::client::*;
+use crate::errors::*;
+use crate::number::Number;
+use crate::symbol::{self, Symbol};
+use log::*;
+use serde_json::Value;
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TradingPair { 
-    pub bid: f64,
-    pub bid_size: f64,   
-    pub ask: f64,                   
-    pub ask_size: f64,
-    pub daily_change: f64,
+    pub bid: Number,
+    pub bid_size: Number,   
+    pub ask: Number,                   
+    pub ask_size: Number,
+    pub daily_change: Number,
     pub daily_change_perc: f64,
-    pub last_price: f64,
-    pub volume: f64,
-    pub high: f64,
-    pub low: f64                    
+    pub last_price: Number,
+    pub volume: Number,
+    pub high: Number,
+    pub low: Number                    
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FundingCurrency { 
-    pub frr: f64,
-    pub bid: f64,
+    pub frr: Number,
+    pub bid: Number,
     pub bid_period: i64,
-    pub bid_size: f64,   
-    pub ask: f64,      
+    pub bid_size: Number,   
+    pub ask: Number,      
     pub ask_period: i64,              
-    pub ask_size: f64,
-    pub daily_change: f64,
+    pub ask_size: Number,
+    pub daily_change: Number,
     pub daily_change_perc: f64,
-    pub last_price: f64,
-    pub volume: f64,
-    pub high: f64,
-    pub low: f64
+    pub last_price: Number,
+    pub volume: Number,
+    pub high: Number,
+    pub low: Number,
+
+    #[serde(skip_serializing, default)]
+    _placeholder_1: Option<Value>,
+    #[serde(skip_serializing, default)]
+    _placeholder_2: Option<Value>,
+
+    #[serde(default)]
+    pub frr_amount_available: Option<Number>,
+}
+
+/// Ticker of either kind, as returned by `tickers`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum TickerData {
+    Trading(TradingPair),
+    Funding(FundingCurrency),
+}
+
+/// Best bid and ask of a trading pair at `mts`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TickerHistory {
+    pub symbol: symbol::TradingPair,
+    pub bid: Option<Number>,
+    #[serde(skip_serializing)]
+    _placeholder_1: Option<Value>,
+    pub ask: Option<Number>,
+    #[serde(skip_serializing)]
+    _placeholder_2: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_3: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_4: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_5: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_6: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_7: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_8: Option<Value>,
+    #[serde(skip_serializing)]
+    _placeholder_9: Option<Value>,
+    pub mts: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TickersHistoryParams {
+    /// Number of snapshots requested (Max: 250)
+    pub limit: Option<i32>,
+
+    /// Filter start (ms)
+    pub start: Option<i64>,
+
+    /// Filter end (ms)
+    pub end: Option<i64>,
+}
+
+impl TickersHistoryParams {
+    pub fn new() -> Self {
+        Self {
+            limit: Some(100),
+            start: None,
+            end: None,
+        }
+    }
+
+    pub fn to_query(&self) -> String {
+        format!("{}={}&{}={}&{}={}",
+            "limit", self.limit
+                         .map(|a| a.to_string())
+                         .unwrap_or_default(),
+            "start", self.start
+                         .map(|a| a.to_string())
+                         .unwrap_or_default(),
+            "end", self.end
+                       .map(|a| a.to_string())
+                       .unwrap_or_default(),
+        )
+    }
 }
 
 #[derive(Clone)]
@@ -38,32 +121,155 @@ pub struct Ticker {
     client: Client,
 }
 
+impl Default for Ticker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Ticker {
     pub fn new() -> Self {
+        Ticker::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
         Ticker {
-            client: Client::new(None, None),
+            client: Client::with_config(None, None, config),
         }
     }
 
-    pub fn funding_currency<S>(&self, symbol: S) -> Result<FundingCurrency>
-        where S: Into<String>
+    pub fn funding_currency<C>(&self, currency: C) -> Result<FundingCurrency>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
     {     
-        let endpoint: String = format!("ticker/f{}", symbol.into());
+        let endpoint: String = format!("ticker/{}", currency.try_into()?);
         let data = self.client.get(endpoint, String::new())?;
 
-        let ticker: FundingCurrency = from_str(data.as_str())?;
+        let ticker: FundingCurrency = decode(data.as_str())?;
 
         Ok(ticker)
     }    
 
-    pub fn trading_pair<S>(&self, symbol: S) -> Result<TradingPair>
-        where S: Into<String>
+    pub fn trading_pair<P>(&self, pair: P) -> Result<TradingPair>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
     {     
-        let endpoint: String = format!("ticker/t{}", symbol.into());
+        let endpoint: String = format!("ticker/{}", pair.try_into()?);
         let data = self.client.get(endpoint, String::new())?;
 
-        let ticker: TradingPair = from_str(data.as_str())?;
+        let ticker: TradingPair = decode(data.as_str())?;
 
         Ok(ticker)
     }
-}
\ No newline at end of file
+
+    /// Tickers of the given trading pairs and funding currencies.
+    pub fn tickers(&self, symbols: &[Symbol]) -> Result<HashMap<Symbol, TickerData>> {
+        self.fetch_tickers(&symbols_query(symbols))
+    }
+
+    /// Tickers of every trading pair and funding currency.
+    pub fn all_tickers(&self) -> Result<HashMap<Symbol, TickerData>> {
+        self.fetch_tickers(ALL_SYMBOLS)
+    }
+
+    fn fetch_tickers(&self, symbols: &str) -> Result<HashMap<Symbol, TickerData>> {
+        let data = self.client.get("tickers".into(), format!("symbols={}", symbols))?;
+
+        parse_tickers(decode(data.as_str())?)
+    }
+
+    /// Bid and ask snapshots of the given trading pairs, every pair when empty.
+    pub fn history(
+        &self,
+        pairs: &[symbol::TradingPair],
+        params: &TickersHistoryParams,
+    ) -> Result<Vec<TickerHistory>> {
+        let data = self.client.get("tickers/hist".into(), history_query(pairs, params))?;
+
+        let history: Vec<TickerHistory> = decode(data.as_str())?;
+
+        Ok(history)
+    }
+}
+
+pub(crate) static ALL_SYMBOLS: &str = "ALL";
+
+pub(crate) fn symbols_query(symbols: &[Symbol]) -> String {
+    symbols.iter().map(Symbol::to_string).collect::<Vec<_>>().join(",")
+}
+
+pub(crate) fn history_query(pairs: &[symbol::TradingPair], params: &TickersHistoryParams) -> String {
+    let symbols = if pairs.is_empty() {
+        ALL_SYMBOLS.to_owned()
+    } else {
+        pairs.iter().map(symbol::TradingPair::to_string).collect::<Vec<_>>().join(",")
+    };
+
+    format!("symbols={}&{}", symbols, params.to_query())
+}
+
+/// Splits the `[SYMBOL, ...ticker]` entries of a `tickers` answer. Entries whose
+/// symbol or fields can't be parsed are skipped rather than failing the whole list.
+pub(crate) fn parse_tickers(entries: Vec<Vec<Value>>) -> Result<HashMap<Symbol, TickerData>> {
+    let mut tickers = HashMap::with_capacity(entries.len());
+
+    for mut entry in entries {
+        if entry.is_empty() {
+            continue;
+        }
+
+        let raw_symbol = entry.remove(0);
+        let symbol: Symbol = match raw_symbol.as_str().map(str::parse) {
+            Some(Ok(symbol)) => symbol,
+            _ => {
+                warn!("Skipping ticker with unexpected symbol {}", raw_symbol);
+                continue;
+            }
+        };
+
+        let ticker = if symbol.is_funding() {
+            serde_json::from_value(Value::Array(entry)).map(TickerData::Funding)
+        } else {
+            serde_json::from_value(Value::Array(entry)).map(TickerData::Trading)
+        };
+
+        match ticker {
+            Ok(ticker) => {
+                tickers.insert(symbol, ticker);
+            }
+            Err(e) => warn!("Skipping ticker of {} with unexpected shape: {}", symbol, e),
+        }
+    }
+
+    Ok(tickers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::from_str;
+    use crate::pairs::BTCUSD;
+
+    #[test]
+    fn test_tickers() {
+        let data = "[[\"tBTCUSD\",7616.5,31.89,7617.5,43.35,-550.8,-0.0674,7617.1,8314.71,8257.8,7500],[\"fUSD\",0.0003447013698630137,0.000316,30,1746436.3,0.000322,2,7004.9,0.00001687,0.0553,0.000322,116945440.8,0.000382,0.000188,null,null,1060286.7]]";
+        let tickers = parse_tickers(from_str(data).expect("parsed")).unwrap();
+
+        assert!(matches!(tickers[&Symbol::Trading(BTCUSD)], TickerData::Trading(_)));
+        match tickers[&"fUSD".parse().unwrap()] {
+            TickerData::Funding(ref ticker) => assert!(ticker.frr_amount_available.is_some()),
+            _ => panic!("expected a funding ticker"),
+        }
+
+        let data = "[[\"tBTCUSD\",7616.5,31.89,7617.5,43.35,-550.8,-0.0674,7617.1,8314.71,8257.8,7500],[\"tETHUSD\",\"halted\"]]";
+        let tickers = parse_tickers(from_str(data).expect("parsed")).unwrap();
+        assert_eq!(tickers.len(), 1);
+    }
+
+    #[test]
+    fn test_tickers_history() {
+        let data = "[[\"tBTCUSD\",54281,null,54282,null,null,null,null,null,null,null,null,1619018394000]]";
+        let history: Vec<TickerHistory> = from_str(data).expect("parsed");
+        assert_eq!(history[0].symbol, BTCUSD);
+        assert_eq!(history[0].mts, 1619018394000);
+        assert_eq!(history_query(&[], &TickersHistoryParams::new()), "symbols=ALL&limit=100&start=&end=");
+    }
+}
@@ -1,6 +1,11 @@
-use client::*;
-use errors::*;
-use serde_json::from_str;
+use crate::client::*;
+use crate::errors::*;
+use crate::history::{HistoryPager, PageParams};
+use crate::number::Number;
+use crate::symbol;
+use serde::de::DeserializeOwned;
+
+pub use crate::history::HistoryEntry;
 
 #[derive(Serialize, Deserialize)]
 pub struct Trade {
@@ -8,12 +13,12 @@ pub struct Trade {
     pub pair: String,
     pub execution_timestamp: i64,
     pub order_id: i32,
-    pub execution_amount: f64,
-    pub execution_price: f64,
+    pub execution_amount: Number,
+    pub execution_price: Number,
     pub order_type: String,
-    pub order_price: f64,
+    pub order_price: Number,
     pub maker: i32,
-    pub fee: f64,
+    pub fee: Number,
     pub fee_currency: String
 }
 
@@ -21,70 +26,252 @@ pub struct Trade {
 pub struct TradingPair {
     pub id: i64,
     pub mts: i64,
-    pub amount: f64,
-    pub price: f64
+    pub amount: Number,
+    pub price: Number
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FundingCurrency {
+    pub id: i64,
     pub mts: i64,
-    pub amount: f64,
-    pub price: f64,
-    pub rate: f64,
+    pub amount: Number,
+    pub rate: Number,
     pub period: i64
 }
 
+impl HistoryEntry for TradingPair {
+    fn id(&self) -> i64 {
+        self.id
+    }
+
+    fn mts(&self) -> i64 {
+        self.mts
+    }
+}
+
+impl HistoryEntry for FundingCurrency {
+    fn id(&self) -> i64 {
+        self.id
+    }
+
+    fn mts(&self) -> i64 {
+        self.mts
+    }
+}
+
+static DEFAULT_LIMIT: i32 = 125;
+
+#[derive(Debug, Clone, Default)]
+pub struct TradeHistoryParams {
+    /// Number of trades requested (Max: 10000)
+    pub limit: Option<i32>,
+
+    /// Filter start (ms)
+    pub start: Option<i64>,
+
+    /// Filter end (ms)
+    pub end: Option<i64>,
+
+    /// Sorts the results from old > new
+    pub sort: Option<bool>,
+}
+
+impl TradeHistoryParams {
+    pub fn new() -> Self {
+        Self {
+            limit: Some(DEFAULT_LIMIT),
+            sort: Some(false),
+            start: None,
+            end: None,
+        }
+    }
+
+    pub fn to_query(&self) -> String {
+        format!("{}={}&{}={}&{}={}&{}={}",
+            "limit", self.limit
+                         .map(|a| a.to_string())
+                         .unwrap_or_default(),
+            "start", self.start
+                         .map(|a| a.to_string())
+                         .unwrap_or_default(),
+            "end", self.end
+                       .map(|a| a.to_string())
+                       .unwrap_or_default(),
+            "sort", self.sort
+                        .map(|a| if a { "1" } else { "-1" })
+                        .unwrap_or(""),
+        )
+    }
+}
+
+impl PageParams for TradeHistoryParams {
+    const MAX_LIMIT: i32 = 10000;
+
+    fn limit(&self) -> i32 {
+        self.limit.unwrap_or(DEFAULT_LIMIT)
+    }
+
+    fn set_limit(&mut self, limit: i32) {
+        self.limit = Some(limit);
+    }
+
+    fn set_start(&mut self, start: i64) {
+        self.start = Some(start);
+    }
+
+    fn set_end(&mut self, end: i64) {
+        self.end = Some(end);
+    }
+
+    fn ascending(&self) -> bool {
+        self.sort == Some(true)
+    }
+}
+
+/// Paging state behind `TradeHistoryIter` and the async pages, see `HistoryPager`.
+#[derive(Debug)]
+pub(crate) struct TradePager {
+    endpoint: String,
+    pager: HistoryPager<TradeHistoryParams>,
+}
+
+impl TradePager {
+    pub(crate) fn new(endpoint: String, params: TradeHistoryParams) -> Self {
+        TradePager { endpoint, pager: HistoryPager::new(params) }
+    }
+
+    /// Endpoint and query of the next page, `None` once the range is exhausted.
+    pub(crate) fn next_request(&self) -> Option<(String, String)> {
+        self.pager.next_params().map(|params| (self.endpoint.clone(), params.to_query()))
+    }
+
+    pub(crate) fn finish(&mut self) {
+        self.pager.finish();
+    }
+
+    /// Drops the trades already returned and moves the window past `page`.
+    pub(crate) fn advance<T: HistoryEntry>(&mut self, page: Vec<T>) -> Result<Vec<T>> {
+        self.pager.advance(page)
+    }
+}
+
+/// Walks through the trades of a symbol page by page, see `Trades::trading_pair_iter`.
+pub struct TradeHistoryIter<'a, T> {
+    trades: &'a Trades,
+    pager: TradePager,
+    page: std::vec::IntoIter<T>,
+}
+
+impl<T: HistoryEntry + DeserializeOwned> Iterator for TradeHistoryIter<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        loop {
+            if let Some(trade) = self.page.next() {
+                return Some(Ok(trade));
+            }
+
+            let (endpoint, query) = self.pager.next_request()?;
+            let page = self.trades.client.get(endpoint, query)
+                .and_then(|data| decode::<Vec<T>>(data.as_str()))
+                .and_then(|page| self.pager.advance(page));
+
+            match page {
+                Ok(page) => self.page = page.into_iter(),
+                Err(e) => {
+                    self.pager.finish();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Trades {
     client: Client,
 }
 
+impl Default for Trades {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Trades {
     pub fn new() -> Self {
+        Trades::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> Self {
         Trades {
-            client: Client::new(None, None),
+            client: Client::with_config(None, None, config),
         }
     }
 
-    pub fn funding_currency<S>(&self, symbol: S) -> Result<Vec<FundingCurrency>>
-        where S: Into<String>
+    pub fn funding_currency<C>(&self, currency: C, params: &TradeHistoryParams) -> Result<Vec<FundingCurrency>>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
     {     
-        let endpoint: String = format!("trades/f{}/hist", symbol.into());
-        let data = self.client.get(endpoint, String::new())?;
+        let endpoint: String = format!("trades/{}/hist", currency.try_into()?);
+        let data = self.client.get(endpoint, params.to_query())?;
 
-        let trades: Vec<FundingCurrency> = from_str(data.as_str())?;
+        let trades: Vec<FundingCurrency> = decode(data.as_str())?;
 
         Ok(trades)
     }
 
-    pub fn trading_pair<S>(&self, symbol: S) -> Result<Vec<TradingPair>>
-        where S: Into<String>
+    pub fn trading_pair<P>(&self, pair: P, params: &TradeHistoryParams) -> Result<Vec<TradingPair>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
     {
-        let endpoint: String = format!("trades/t{}/hist", symbol.into());
-        let data = self.client.get(endpoint, String::new())?;
+        let endpoint: String = format!("trades/{}/hist", pair.try_into()?);
+        let data = self.client.get(endpoint, params.to_query())?;
 
-        let trades: Vec<TradingPair> = from_str(data.as_str())?;
+        let trades: Vec<TradingPair> = decode(data.as_str())?;
 
         Ok(trades)
     }
 
-    pub fn history<S>(&self, symbol: S) -> Result<Vec<Trade>>
-        where S: Into<String>
+    /// Iterates over every trade between `params.start` and `params.end`, newest
+    /// first unless `params.sort` is set, requesting `params.limit` trades at a time.
+    pub fn trading_pair_iter<P>(&self, pair: P, params: TradeHistoryParams) -> Result<TradeHistoryIter<'_, TradingPair>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
     {
-        let payload: String = format!("{}", "{}");
+        let endpoint: String = format!("trades/{}/hist", pair.try_into()?);
+        Ok(self.iter(endpoint, params))
+    }
 
-        let request: String = format!("trades/t{}/hist", symbol.into());
-        println!("HISTORY {}", request);
-        return self.trades(request, payload);
+    /// Same as `trading_pair_iter` for funding trades.
+    pub fn funding_currency_iter<C>(&self, currency: C, params: TradeHistoryParams) -> Result<TradeHistoryIter<'_, FundingCurrency>>
+        where C: TryInto<symbol::FundingCurrency>, Error: From<C::Error>
+    {
+        let endpoint: String = format!("trades/{}/hist", currency.try_into()?);
+        Ok(self.iter(endpoint, params))
     }
 
-    pub fn generated_by_order<S>(&self, symbol: S, order_id: S) -> Result<Vec<Trade>>
-        where S: Into<String>
+    fn iter<T>(&self, endpoint: String, params: TradeHistoryParams) -> TradeHistoryIter<'_, T> {
+        TradeHistoryIter {
+            trades: self,
+            pager: TradePager::new(endpoint, params),
+            page: Vec::new().into_iter(),
+        }
+    }
+
+    pub fn history<P>(&self, pair: P) -> Result<Vec<Trade>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>
+    {
+        let payload: String = "{}".to_string();
+
+        let request: String = format!("trades/{}/hist", pair.try_into()?);
+        self.trades(request, payload)
+    }
+
+    pub fn generated_by_order<P, S>(&self, pair: P, order_id: S) -> Result<Vec<Trade>>
+        where P: TryInto<symbol::TradingPair>, Error: From<P::Error>, S: Into<String>
     {
-        let payload: String = format!("{}", "{}");
+        let payload: String = "{}".to_string();
 
-        let request: String = format!("order/t{}:{}/trades", symbol.into(), order_id.into());
-        return self.trades(request, payload);
+        let request: String = format!("order/{}:{}/trades", pair.try_into()?, order_id.into());
+        self.trades(request, payload)
     }   
 
     pub fn trades<S>(&self, request: S, payload: S) -> Result<Vec<Trade>>
@@ -92,8 +279,32 @@ impl Trades {
     {
         let data = self.client.post_signed(request.into(), payload.into())?;
 
-        let orders: Vec<Trade> = from_str(data.as_str())?;
+        let orders: Vec<Trade> = decode(data.as_str())?;
 
         Ok(orders)
     }    
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(id: i64, mts: i64) -> TradingPair {
+        TradingPair { id, mts, amount: Number::default(), price: Number::default() }
+    }
+
+    #[test]
+    fn test_pager_requests() {
+        let params = TradeHistoryParams { limit: Some(2), sort: Some(true), start: Some(10), ..TradeHistoryParams::new() };
+        let mut pager = TradePager::new("trades/tBTCUSD/hist".into(), params);
+
+        pager.advance(vec![trade(1, 10), trade(2, 10)]).unwrap();
+        assert_eq!(pager.next_request().unwrap().1, "limit=2&start=10&end=&sort=1");
+
+        assert!(pager.advance(vec![trade(1, 10), trade(2, 10)]).unwrap().is_empty());
+        assert_eq!(pager.next_request().unwrap(), ("trades/tBTCUSD/hist".into(), "limit=10000&start=10&end=10&sort=1".into()));
+
+        pager.advance(vec![trade(1, 10), trade(2, 10), trade(3, 10)]).unwrap();
+        assert_eq!(pager.next_request().unwrap().1, "limit=2&start=11&end=&sort=1");
+    }
+}
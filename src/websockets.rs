@@ -1,21 +1,24 @@
-use url::Url;
-use errors::*;
-use events::*;
-use serde_json::from_str;
-use auth;
+use crate::errors::*;
+use crate::events::*;
+use serde_json::Value;
+use crate::auth::{self, NonceProvider};
+use crate::client::ClientConfig;
+use crate::candles::CandleKey;
+use crate::derivatives::StatusKey;
+use crate::symbol::Symbol;
 
 use tungstenite::connect;
 use tungstenite::Message;
 use tungstenite::protocol::WebSocket;
-use tungstenite::client::AutoStream;
+use tungstenite::stream::MaybeTlsStream;
 use tungstenite::handshake::client::Response;
 
+use std::net::TcpStream;
 use std::sync::mpsc::{self, channel};
 
-static INFO: &'static str = "info";
-static SUBSCRIBED: &'static str = "subscribed";
-static AUTH: &'static str = "auth";
-static WEBSOCKET_URL: &'static str = "wss://api.bitfinex.com/ws/2";
+static INFO: &str = "info";
+static SUBSCRIBED: &str = "subscribed";
+static AUTH: &str = "auth";
 static DEAD_MAN_SWITCH_FLAG: u8 = 4;
 
 pub trait EventHandler {
@@ -23,12 +26,7 @@ pub trait EventHandler {
     fn on_auth(&mut self, event: NotificationEvent);
     fn on_subscribed(&mut self, event: NotificationEvent);
     fn on_data_event(&mut self, event: DataEvent);
-    fn on_error(&mut self, message: Error); 
-}
-
-pub enum EventType {
-    Funding,
-    Trading
+    fn on_error(&mut self, message: Error);
 }
 
 #[derive(Debug)]
@@ -38,38 +36,47 @@ enum WsMessage {
 }
 
 pub struct WebSockets {
-    socket: Option<(WebSocket<AutoStream>, Response)>,
+    socket: Option<(WebSocket<MaybeTlsStream<TcpStream>>, Response)>,
     sender: Sender,
     rx: mpsc::Receiver<WsMessage>,
-    event_handler: Option<Box< dyn EventHandler>>, 
+    event_handler: Option<Box< dyn EventHandler>>,
+    config: ClientConfig,
+}
+
+impl Default for WebSockets {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl WebSockets {
     pub fn new() -> WebSockets {
+        WebSockets::with_config(ClientConfig::default())
+    }
+
+    pub fn with_config(config: ClientConfig) -> WebSockets {
         let (tx, rx) = channel::<WsMessage>();
         let sender = Sender {
-            tx: tx
+            tx
         };
 
         WebSockets {
             socket: None,
-            sender: sender,
-            rx: rx,
-            event_handler: None
+            sender,
+            rx,
+            event_handler: None,
+            config,
         }
     }
 
     pub fn connect(&mut self) -> Result<()> {
-        let wss: String = format!("{}", WEBSOCKET_URL);
-        let url = Url::parse(&wss)?;
-
-        match connect(url) {
+        match connect(self.config.websocket_url.as_str()) {
             Ok(answer) => {
                 self.socket = Some(answer);
                 Ok(())
             }
             Err(e) => {
-                bail!(format!("Error during handshake {}", e))
+                Err(Error::Other(format!("Error during handshake {}", e)))
             }
         }
     }
@@ -87,7 +94,7 @@ impl WebSockets {
     /// * `api_key` - The API key
     /// * `api_secret` - The API secret
     /// * `dms` - Whether the dead man switch is enabled. If true, all account orders will be
-    ///           cancelled when the socket is closed.
+    ///   cancelled when the socket is closed.
     pub fn auth<S>(
         &mut self,
         api_key: S,
@@ -98,20 +105,7 @@ impl WebSockets {
     where
         S: AsRef<str>,
     {
-        let nonce = auth::generate_nonce()?;
-        let auth_payload = format!("AUTH{}", nonce);
-        let signature =
-            auth::sign_payload(api_secret.as_ref().as_bytes(), auth_payload.as_bytes())?;
-
-        let msg = json!({
-            "event": "auth",
-            "apiKey": api_key.as_ref(),
-            "authSig": signature,
-            "authNonce": nonce,
-            "authPayload": auth_payload,
-            "dms": if dms {Some(DEAD_MAN_SWITCH_FLAG)} else {None},
-            "filters": filters,
-        });
+        let msg = auth_message(self.config.nonce_provider.as_ref(), api_key.as_ref(), api_secret.as_ref(), dms, filters)?;
 
         if let Err(error_msg) = self.sender.send(&msg.to_string()) {
             self.error_hander(error_msg);
@@ -120,63 +114,51 @@ impl WebSockets {
         Ok(())
     }
 
-    pub fn subscribe_ticker<S>(&mut self, symbol: S, et: EventType) where S: Into<String> {
-        let local_symbol = self.format_symbol(symbol.into(), et);
-        let msg = json!({"event": "subscribe", "channel": "ticker", "symbol": local_symbol });
-
-        if let Err(error_msg) = self.sender.send(&msg.to_string()) {
-            self.error_hander(error_msg);
-        }
+    pub fn subscribe_ticker<S>(&mut self, symbol: S)
+        where S: TryInto<Symbol>, Error: From<S::Error>
+    {
+        let msg = symbol.try_into().map(|symbol| subscribe_message("ticker", &symbol));
+        self.send_message(msg.map_err(Error::from));
     }
 
-    pub fn subscribe_trades<S>(&mut self, symbol: S, et: EventType) where S: Into<String> {
-        let local_symbol = self.format_symbol(symbol.into(), et);
-        let msg = json!({"event": "subscribe", "channel": "trades", "symbol": local_symbol });
-
-        if let Err(error_msg) = self.sender.send(&msg.to_string()) {
-            self.error_hander(error_msg);
-        }
+    pub fn subscribe_trades<S>(&mut self, symbol: S)
+        where S: TryInto<Symbol>, Error: From<S::Error>
+    {
+        let msg = symbol.try_into().map(|symbol| subscribe_message("trades", &symbol));
+        self.send_message(msg.map_err(Error::from));
     }
 
-    pub fn subscribe_candles<S>(&mut self, symbol: S, timeframe: S) where S: Into<String> {
-        let key: String = format!("trade:{}:t{}", timeframe.into(), symbol.into());
-        let msg = json!({"event": "subscribe", "channel": "candles", "key": key });
+    pub fn subscribe_candles(&mut self, key: &CandleKey) {
+        self.send_message(Ok(candles_message(key)));
+    }
 
-        if let Err(error_msg) = self.sender.send(&msg.to_string()) {
-            self.error_hander(error_msg);
-        } 
+    /// Subscribes to derivatives status (`deriv:tBTCF0:USTF0`) or liquidation (`liq:global`) updates.
+    pub fn subscribe_status(&mut self, key: &StatusKey) {
+        self.send_message(Ok(status_message(key)));
     }
 
-    pub fn subscribe_books<S, P, F>(&mut self, symbol: S, et: EventType, prec: P, freq: F, len: u32) 
-        where S: Into<String>, P: Into<String>, F: Into<String> 
+    pub fn subscribe_books<S, P, F>(&mut self, symbol: S, prec: P, freq: F, len: u32)
+        where S: TryInto<Symbol>, Error: From<S::Error>, P: Into<String>, F: Into<String>
     {
-        let msg = json!(
-            {
-                "event": "subscribe", 
-                "channel": "book", 
-                "symbol": self.format_symbol(symbol.into(), et),
-                "prec": prec.into(),
-                "freq": freq.into(),
-                "len": len
-            });
-
-        if let Err(error_msg) = self.sender.send(&msg.to_string()) {
-            self.error_hander(error_msg);
-        }
+        let msg = symbol.try_into().map(|symbol| books_message(&symbol, prec.into(), freq.into(), len));
+        self.send_message(msg.map_err(Error::from));
     }
 
-    pub fn subscribe_raw_books<S>(&mut self, symbol: S, et: EventType)
-        where S: Into<String> 
+    pub fn subscribe_raw_books<S>(&mut self, symbol: S)
+        where S: TryInto<Symbol>, Error: From<S::Error>
     {
-        let msg = json!(
-            {
-                "event": "subscribe", 
-                "channel": "book", 
-                "prec": "R0",
-                "pair": self.format_symbol(symbol.into(), et)
-            });
+        let msg = symbol.try_into().map(|symbol| raw_books_message(&symbol));
+        self.send_message(msg.map_err(Error::from));
+    }
 
-        if let Err(error_msg) = self.sender.send(&msg.to_string()) {
+    /// Unsubscribes from the channel with the `chan_id` returned in the subscribed event.
+    pub fn unsubscribe(&mut self, chan_id: u32) {
+        self.send_message(Ok(unsubscribe_message(chan_id)));
+    }
+
+    /// Queues a message, reporting a failure to build or send it to the event handler.
+    fn send_message(&mut self, msg: Result<Value>) {
+        if let Err(error_msg) = msg.and_then(|msg| self.sender.send(&msg.to_string())) {
             self.error_hander(error_msg);
         }
     }
@@ -184,16 +166,7 @@ impl WebSockets {
     fn error_hander(&mut self, error_msg: Error) {
         if let Some(ref mut h) = self.event_handler {
             h.on_error(error_msg);
-        }        
-    }
-
-    fn format_symbol(&mut self, symbol: String, et: EventType) -> String {
-        let local_symbol = match et {
-            EventType::Funding => format!("f{}", symbol),
-            EventType::Trading => format!("t{}", symbol),
-        };
-
-        local_symbol
+        }
     }
 
     pub fn event_loop(&mut self) -> Result<()>  {
@@ -204,7 +177,7 @@ impl WebSockets {
                         Ok(msg) => {
                             match msg {
                                 WsMessage::Text(text) => {
-                                    socket.0.write_message(Message::Text(text))?;
+                                    socket.0.send(Message::Text(text))?;
                                 }
                                 WsMessage::Close => {
                                     return socket.0.close(None).map_err(|e| e.into());
@@ -212,46 +185,37 @@ impl WebSockets {
                             }
                         }
                         Err(mpsc::TryRecvError::Disconnected) => {
-                            bail!("Disconnected")
+                            return Err(Error::Other("Disconnected".into()))
                         }
                         Err(mpsc::TryRecvError::Empty) => break,
                     }
                 }
 
-                let message = socket.0.read_message()?;
+                let message = socket.0.read()?;
 
                 match message {
                     Message::Text(text) => {
                         if let Some(ref mut h) = self.event_handler {
-                            if text.find(INFO) != None {
-                                let event: NotificationEvent = from_str(&text)?;
-                                h.on_connect(event);
-                            } else if text.find(SUBSCRIBED) != None {
-                                let event: NotificationEvent = from_str(&text)?;
-                                h.on_subscribed(event);
-                            } else if text.find(AUTH).is_some() {
-                                let event: NotificationEvent = from_str(&text)?;
-                                h.on_auth(event);
-                            } else {
-                                let event: DataEvent = from_str(&text)?;
-                                if let DataEvent::HeartbeatEvent(_a,_b) = event {
-                                    continue;
-                                } else {
-                                    h.on_data_event(event);
-                                }
+                            match parse_event(&text)? {
+                                Some(WsEvent::Connected(event)) => h.on_connect(event),
+                                Some(WsEvent::Subscribed(event)) => h.on_subscribed(event),
+                                Some(WsEvent::Auth(event)) => h.on_auth(event),
+                                Some(WsEvent::Data(event)) => h.on_data_event(event),
+                                None => continue,
                             }
                         }
                     }
                     Message::Binary(_) => {}
                     Message::Ping(_) |
-                    Message::Pong(_) => {}
+                    Message::Pong(_) |
+                    Message::Frame(_) => {}
                     Message::Close(e) => {
-                        bail!(format!("Disconnected {:?}", e));
+                        return Err(Error::Other(format!("Disconnected {:?}", e)));
                     }
                 }
             }
         }
-    } 
+    }
 }
 
 
@@ -263,12 +227,128 @@ pub struct Sender {
 impl Sender {
     pub fn send(&self, raw: &str) -> Result<()> {
         self.tx.send(WsMessage::Text(raw.to_string()))
-            .map_err(|e| Error::with_chain(e, "Not able to send a message"))?;
+            .map_err(|e| Error::Other(format!("Not able to send a message: {}", e)))?;
         Ok(())
     }
 
     pub fn shutdown(&self) -> Result<()> {
         self.tx.send(WsMessage::Close)
-            .map_err(|e| Error::with_chain(e, "Error during shutdown"))
+            .map_err(|e| Error::Other(format!("Error during shutdown: {}", e)))
+    }
+}
+
+/// Parses a text frame into a `WsEvent`. Heartbeats are swallowed and yield `None`.
+pub(crate) fn parse_event(text: &str) -> Result<Option<WsEvent>> {
+    let event = if text.contains(INFO) {
+        WsEvent::Connected(decode(text)?)
+    } else if text.contains(SUBSCRIBED) {
+        WsEvent::Subscribed(decode(text)?)
+    } else if text.contains(AUTH) {
+        WsEvent::Auth(decode(text)?)
+    } else {
+        match decode(text)? {
+            DataEvent::HeartbeatEvent(_, _) => return Ok(None),
+            event => WsEvent::Data(event),
+        }
+    };
+
+    Ok(Some(event))
+}
+
+pub(crate) fn auth_message(
+    nonce_provider: &dyn NonceProvider,
+    api_key: &str,
+    api_secret: &str,
+    dms: bool,
+    filters: &[&str],
+) -> Result<Value> {
+    let nonce = nonce_provider.next_nonce()?.to_string();
+    let auth_payload = format!("AUTH{}", nonce);
+    let signature = auth::sign_payload(api_secret.as_bytes(), auth_payload.as_bytes())?;
+
+    Ok(json!({
+        "event": "auth",
+        "apiKey": api_key,
+        "authSig": signature,
+        "authNonce": nonce,
+        "authPayload": auth_payload,
+        "dms": if dms {Some(DEAD_MAN_SWITCH_FLAG)} else {None},
+        "filters": filters,
+    }))
+}
+
+pub(crate) fn subscribe_message(channel: &str, symbol: &Symbol) -> Value {
+    json!({"event": "subscribe", "channel": channel, "symbol": symbol })
+}
+
+pub(crate) fn candles_message(key: &CandleKey) -> Value {
+    json!({"event": "subscribe", "channel": "candles", "key": key.to_string() })
+}
+
+pub(crate) fn status_message(key: &StatusKey) -> Value {
+    json!({"event": "subscribe", "channel": "status", "key": key.to_string() })
+}
+
+pub(crate) fn books_message(symbol: &Symbol, prec: String, freq: String, len: u32) -> Value {
+    json!(
+        {
+            "event": "subscribe",
+            "channel": "book",
+            "symbol": symbol,
+            "prec": prec,
+            "freq": freq,
+            "len": len
+        })
+}
+
+pub(crate) fn raw_books_message(symbol: &Symbol) -> Value {
+    json!(
+        {
+            "event": "subscribe",
+            "channel": "book",
+            "prec": "R0",
+            "pair": symbol
+        })
+}
+
+pub(crate) fn unsubscribe_message(chan_id: u32) -> Value {
+    json!({"event": "unsubscribe", "chanId": chan_id })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event() {
+        let info = "{\"event\":\"info\",\"version\":2,\"serverId\":\"abc\",\"platform\":{\"status\":1}}";
+        assert!(matches!(parse_event(info).expect("parsed"), Some(WsEvent::Connected(_))));
+
+        let heartbeat = "[17470,\"hb\"]";
+        assert!(parse_event(heartbeat).expect("parsed").is_none());
+
+        let ticker = "[1,[7616.5,31.89,7617.5,43.35,-550.8,-0.0674,7617.1,8314.71,8257.8,7500]]";
+        assert!(matches!(parse_event(ticker).expect("parsed"), Some(WsEvent::Data(DataEvent::TickerTradingEvent(1, _)))));
+    }
+
+    #[test]
+    fn test_parse_status_event() {
+        let subscribed = "{\"event\":\"subscribed\",\"channel\":\"status\",\"chanId\":7,\"key\":\"liq:global\"}";
+        assert!(matches!(
+            parse_event(subscribed).expect("parsed"),
+            Some(WsEvent::Subscribed(NotificationEvent::StatusSubscribed(_)))
+        ));
+
+        let candles = "{\"event\":\"subscribed\",\"channel\":\"candles\",\"chanId\":8,\"key\":\"trade:1m:tBTCUSD\"}";
+        assert!(matches!(
+            parse_event(candles).expect("parsed"),
+            Some(WsEvent::Subscribed(NotificationEvent::CandlesSubscribed(_)))
+        ));
+
+        let deriv = "[7,[1605788850000,null,18315.5,18309.4,null,17283.82,null,1605801600000,0.00005471,9,null,0.00001012,null,null,18312.16,null,null,2431.54,null,null,null,0.005,0.01]]";
+        assert!(matches!(parse_event(deriv).expect("parsed"), Some(WsEvent::Data(DataEvent::DerivStatusEvent(7, _)))));
+
+        let liq = "[7,[[\"pos\",145400868,1609144352338,null,\"tBTCF0:USTF0\",-0.001,28104,null,1,1,null,33331.7]]]";
+        assert!(matches!(parse_event(liq).expect("parsed"), Some(WsEvent::Data(DataEvent::LiquidationEvent(7, _)))));
     }
 }